@@ -0,0 +1,89 @@
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Example fixtures live in `tests/examples/y<year>/d<day>/`. `input.txt` is
+/// the example and `p1.txt`/`p2.txt` are the expected answers. More cases can
+/// go in the same directory as `input-<name>.txt`, `p1-<name>.txt` and
+/// `p2-<name>.txt`. A test that can't pass yet can be skipped by putting the
+/// reason in `p1.ignore` or `p2.ignore` (or `p1-<name>.ignore`).
+const EXAMPLES_DIR: &str = "tests/examples";
+
+fn sorted_entries(dir: &Path) -> Vec<PathBuf> {
+    let mut entries = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect::<Vec<_>>(),
+        Err(_) => Vec::new(),
+    };
+    entries.sort();
+    entries
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={EXAMPLES_DIR}");
+
+    let mut tests = String::new();
+    for year_dir in sorted_entries(Path::new(EXAMPLES_DIR)) {
+        let year = year_dir.file_name().unwrap().to_string_lossy().into_owned();
+        for day_dir in sorted_entries(&year_dir) {
+            let day = day_dir.file_name().unwrap().to_string_lossy().into_owned();
+            for input in sorted_entries(&day_dir) {
+                let file_name = input.file_name().unwrap().to_string_lossy().into_owned();
+                let Some(case) = file_name
+                    .strip_prefix("input")
+                    .and_then(|rest| rest.strip_suffix(".txt"))
+                else {
+                    continue;
+                };
+
+                for part in ["p1", "p2"] {
+                    let answer = day_dir.join(format!("{part}{case}.txt"));
+                    if !answer.is_file() {
+                        continue;
+                    }
+
+                    let test_name = format!("{year}_{day}_{part}{case}")
+                        .replace(|ch: char| !ch.is_ascii_alphanumeric(), "_");
+                    let part_variant = format!("Part{}", &part[1..]);
+                    let ignore = day_dir.join(format!("{part}{case}.ignore"));
+                    if let Ok(reason) = fs::read_to_string(&ignore) {
+                        writeln!(tests, "#[ignore = {:?}]", reason.trim()).unwrap();
+                    }
+                    writeln!(
+                        tests,
+                        "#[test]\n\
+                         fn {test_name}() {{\n    \
+                             super::check(\n        \
+                                 crate::Year::{year_variant},\n        \
+                                 crate::Day::{day_variant},\n        \
+                                 crate::Part::{part_variant},\n        \
+                                 include_str!({input:?}),\n        \
+                                 include_str!({answer:?}),\n    \
+                             );\n\
+                         }}\n",
+                        year_variant = capitalize(&year),
+                        day_variant = capitalize(&day),
+                        input = fs::canonicalize(&input).unwrap(),
+                        answer = fs::canonicalize(&answer).unwrap(),
+                    )
+                    .unwrap();
+                }
+            }
+        }
+    }
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    fs::write(out_dir.join("examples.rs"), tests).unwrap();
+}
@@ -0,0 +1,144 @@
+use std::{fmt::Display, time::Duration};
+
+use serde::Serialize;
+
+use crate::{util::ocr, Day, Part, Year};
+
+/// What a solver comes up with, kept typed so that it can be handed on as
+/// JSON without guessing from its text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// Letters drawn in `#`s, along with what they spell
+    Art {
+        art: String,
+        text: String,
+    },
+}
+
+impl Answer {
+    /// The drawing the answer was read from, if it was drawn
+    pub fn art(&self) -> Option<&str> {
+        match self {
+            Self::Art { art, .. } => Some(art),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{number}"),
+            Self::Text(text) | Self::Art { text, .. } => write!(f, "{text}"),
+        }
+    }
+}
+
+macro_rules! number_answers {
+    ($($number:ty),*) => {
+        $(
+            impl From<$number> for Answer {
+                fn from(number: $number) -> Self {
+                    Self::Number(number as i128)
+                }
+            }
+        )*
+    };
+}
+
+number_answers!(i32, i64, isize, u32, u64, usize);
+
+/// An answer along with which puzzle it's for and how long it took, as it's
+/// given out in JSON
+#[derive(Debug, Clone, Serialize)]
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub nanos: u64,
+}
+
+impl Solution {
+    pub fn new(year: Year, day: Day, part: Part, answer: Answer, elapsed: Duration) -> Self {
+        Self {
+            year: year.number(),
+            day: day.number(),
+            part: part.number(),
+            answer,
+            nanos: elapsed.as_nanos().try_into().unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// Prints as a sentence, followed by the drawing if the answer was drawn
+impl Display for Solution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The solution for {} day {} part {} is {} (took {:?})",
+            self.year,
+            self.day,
+            self.part,
+            self.answer,
+            Duration::from_nanos(self.nanos)
+        )?;
+        if let Some(art) = self.answer.art() {
+            write!(f, "\n{art}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Text that turns out to be letters drawn in `#`s is read, keeping the
+/// drawing so that it can be checked by eye
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        match ocr::recognize(&text) {
+            Ok(letters) => Self::Art {
+                art: text,
+                text: letters,
+            },
+            Err(_) => Self::Text(text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_with_its_type() {
+        assert_eq!(
+            serde_json::to_string(&Answer::from(3068usize)).unwrap(),
+            r#"{"type":"number","value":3068}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Answer::from("2=-1=0".to_string())).unwrap(),
+            r#"{"type":"text","value":"2=-1=0"}"#
+        );
+    }
+
+    #[test]
+    fn reads_drawn_answers() {
+        let art = "#..#.###\n#..#..#.\n####..#.\n#..#..#.\n#..#..#.\n#..#.###".to_string();
+        let answer = Answer::from(art.clone());
+        assert_eq!(answer.to_string(), "HI");
+        assert_eq!(answer.art(), Some(art.as_str()));
+
+        let elapsed = Duration::from_millis(5);
+        let solution = Solution::new(Year::Y2022, Day::D10, Part::Part2, answer, elapsed);
+        assert_eq!(
+            solution.to_string(),
+            format!("The solution for 2022 day 10 part 2 is HI (took 5ms)\n{art}")
+        );
+        assert_eq!(
+            serde_json::to_value(&solution).unwrap()["answer"],
+            serde_json::json!({ "type": "art", "value": { "art": art, "text": "HI" } })
+        );
+    }
+}
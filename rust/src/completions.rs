@@ -0,0 +1,108 @@
+use std::io;
+
+use clap::{builder::PossibleValuesParser, CommandFactory, ValueEnum};
+use clap_complete::Shell;
+
+use crate::{registry, Args, Day, Part, Year};
+
+/// The days that have a solution in at least one year
+fn solved_days() -> Vec<Day> {
+    Day::value_variants()
+        .iter()
+        .copied()
+        .filter(|&day| {
+            Year::value_variants().iter().any(|&year| {
+                Part::value_variants()
+                    .iter()
+                    .any(|&part| registry::solver(year, day, part).is_some())
+            })
+        })
+        .collect()
+}
+
+/// Only offers the days we can do something with for every `day` argument,
+/// in this command and all of its subcommands
+fn narrow_days(mut command: clap::Command, days: &[Day]) -> clap::Command {
+    command = command.mut_args(|arg| {
+        if arg.get_id() == "day" {
+            arg.value_parser(PossibleValuesParser::new(
+                days.iter().filter_map(Day::to_possible_value),
+            ))
+        } else {
+            arg
+        }
+    });
+
+    let subcommands: Vec<String> = command
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_string())
+        .collect();
+    for name in subcommands {
+        command = command.mut_subcommand(name, |subcommand| narrow_days(subcommand, days));
+    }
+    command
+}
+
+/// The CLI as it's described to shells and man pages
+fn command() -> clap::Command {
+    narrow_days(Args::command(), &solved_days())
+}
+
+/// Prints a completion script for a shell
+pub fn completions(shell: Shell) {
+    let mut command = command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, &mut io::stdout());
+}
+
+/// Prints a man page in roff format
+pub fn man() -> eyre::Result<()> {
+    clap_mangen::Man::new(command()).render(&mut io::stdout())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day_values(command: &clap::Command) -> Vec<String> {
+        command
+            .get_arguments()
+            .find(|arg| arg.get_id() == "day")
+            .unwrap()
+            .get_possible_values()
+            .iter()
+            .map(|value| value.get_name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn only_solved_days_are_suggested() {
+        let command = command();
+        command.clone().debug_assert();
+
+        let days = day_values(&command);
+        assert!(days.contains(&"17".to_string()));
+        assert!(!days.contains(&"1".to_string()));
+
+        let history = command.find_subcommand("history").unwrap();
+        assert_eq!(day_values(history), days);
+    }
+
+    #[test]
+    fn generates_for_every_shell() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let mut script = Vec::new();
+            clap_complete::generate(shell, &mut command(), "aoc", &mut script);
+            let script = String::from_utf8(script).unwrap();
+            assert!(
+                script.contains("history"),
+                "{shell} script is missing subcommands"
+            );
+        }
+
+        let mut page = Vec::new();
+        clap_mangen::Man::new(command()).render(&mut page).unwrap();
+        assert!(!page.is_empty());
+    }
+}
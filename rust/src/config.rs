@@ -0,0 +1,383 @@
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    net::{IpAddr, Ipv4Addr},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+
+use clap::ValueEnum;
+use eyre::Context;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Deserializer};
+
+use crate::{runner, visualize, Year};
+
+const FILE_NAME: &str = "aoc.toml";
+
+static SETTINGS: OnceCell<Settings> = OnceCell::new();
+
+/// How answers get printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Where a setting came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Flag,
+    Env,
+    File(PathBuf),
+    Default,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Flag => write!(f, "from a flag"),
+            Self::Env => write!(f, "from the environment"),
+            Self::File(path) => write!(f, "from {}", path.display()),
+            Self::Default => write!(f, "default"),
+        }
+    }
+}
+
+fn deserialize_duration<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|duration| runner::parse_duration(&duration).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+/// The settings given by one source, any of which can be left out
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Layer {
+    pub year: Option<u16>,
+    pub cache_dir: Option<PathBuf>,
+    pub session_file: Option<PathBuf>,
+    pub format: Option<Format>,
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub timeout: Option<Duration>,
+    pub port: Option<u16>,
+    pub host: Option<IpAddr>,
+    pub speed: Option<f64>,
+    pub confirm_submit: Option<bool>,
+}
+
+/// Reads an environment variable, if it's set
+fn var<T>(name: &str, parse: impl FnOnce(&str) -> Result<T, String>) -> eyre::Result<Option<T>> {
+    match env::var(name) {
+        Ok(value) => parse(&value)
+            .map(Some)
+            .map_err(|err| eyre::eyre!("${name} is invalid: {err}")),
+        Err(_) => Ok(None),
+    }
+}
+
+fn from_str<T: FromStr>(value: &str) -> Result<T, String>
+where
+    T::Err: Display,
+{
+    value.parse().map_err(|err: T::Err| err.to_string())
+}
+
+impl Layer {
+    fn from_env() -> eyre::Result<Self> {
+        Ok(Self {
+            year: var("AOC_YEAR", from_str)?,
+            cache_dir: var("AOC_CACHE_DIR", from_str)?,
+            session_file: var("AOC_SESSION_FILE", from_str)?,
+            format: var("AOC_FORMAT", |format| Format::from_str(format, true))?,
+            timeout: var("AOC_TIMEOUT", runner::parse_duration)?,
+            port: var("AOC_PORT", from_str)?,
+            host: var("AOC_HOST", from_str)?,
+            speed: var("AOC_SPEED", visualize::parse_speed)?,
+            confirm_submit: var("AOC_CONFIRM_SUBMIT", from_str)?,
+        })
+    }
+}
+
+/// The config files that apply here, most important first: the nearest
+/// `aoc.toml` from the current directory upward, then the user's own one
+pub fn files() -> Vec<PathBuf> {
+    let project = env::current_dir().ok().and_then(|dir| {
+        dir.ancestors()
+            .map(|dir| dir.join(FILE_NAME))
+            .find(|file| file.is_file())
+    });
+    let user = dirs::config_dir()
+        .map(|dir| dir.join("aoc").join(FILE_NAME))
+        .filter(|file| file.is_file());
+    project.into_iter().chain(user).collect()
+}
+
+/// Every source of settings, most important first
+#[derive(Debug, Clone, Default)]
+pub struct Layers(Vec<(Source, Layer)>);
+
+impl Layers {
+    /// Reads the environment and the config files
+    pub fn load() -> eyre::Result<Self> {
+        let (layers, errors) = Self::load_all();
+        match errors.into_iter().next() {
+            Some((_, err)) => Err(err),
+            None => Ok(layers),
+        }
+    }
+
+    /// Reads the environment and the config files, leaving out the ones that
+    /// can't be read along with why
+    pub fn load_all() -> (Self, Vec<(Source, eyre::Report)>) {
+        let mut layers = Vec::new();
+        let mut errors = Vec::new();
+        match Layer::from_env() {
+            Ok(layer) => layers.push((Source::Env, layer)),
+            Err(err) => errors.push((Source::Env, err)),
+        }
+        for file in files() {
+            let layer = fs::read_to_string(&file)
+                .wrap_err_with(|| format!("couldn't read {}", file.display()))
+                .and_then(|text| {
+                    toml::from_str(&text)
+                        .wrap_err_with(|| format!("couldn't parse {}", file.display()))
+                });
+            match layer {
+                Ok(layer) => layers.push((Source::File(file), layer)),
+                Err(err) => errors.push((Source::File(file), err)),
+            }
+        }
+        (Self(layers), errors)
+    }
+
+    /// Puts the settings given on the command line ahead of everything else
+    pub fn with_flags(mut self, flags: Layer) -> Self {
+        self.0.insert(0, (Source::Flag, flags));
+        self
+    }
+
+    /// Finds the most important source that sets a setting
+    fn find<T>(&self, get: impl Fn(&Layer) -> Option<T>) -> Option<(T, Source)> {
+        self.0
+            .iter()
+            .find_map(|(source, layer)| Some((get(layer)?, source.clone())))
+    }
+}
+
+/// Describes a setting's value the way it would be written in `aoc.toml`
+fn quoted(value: impl Display) -> String {
+    format!("{:?}", value.to_string())
+}
+
+/// The settings that everything actually uses, after merging all the sources
+#[derive(Debug, Clone)]
+pub struct Settings {
+    /// The year to use when it's left out of a command
+    pub year: Option<Year>,
+    /// Where downloaded inputs and puzzles are kept
+    pub cache_dir: PathBuf,
+    /// A file to read the session cookie from instead of the one built in
+    pub session_file: Option<PathBuf>,
+    pub format: Format,
+    /// How long `aoc serve` waits for a solve
+    pub timeout: Duration,
+    pub port: u16,
+    pub host: IpAddr,
+    /// How many steps per second `--visualize` starts at
+    pub speed: f64,
+    /// Whether to ask before submitting an answer
+    // nothing submits answers yet, but the setting can already be configured
+    #[allow(dead_code)]
+    pub confirm_submit: bool,
+
+    /// Each setting's name, value (if it has one) and source, in order
+    shown: Vec<(&'static str, Option<String>, Source)>,
+}
+
+impl Settings {
+    pub fn resolve(layers: &Layers) -> eyre::Result<Self> {
+        let mut shown = Vec::new();
+        let mut setting = |name, found: Option<(String, Source)>| match found {
+            Some((value, source)) => shown.push((name, Some(value), source)),
+            None => shown.push((name, None, Source::Default)),
+        };
+
+        let year = match layers.find(|layer| layer.year) {
+            Some((year, source)) => {
+                let parsed = Year::from_str(&year.to_string(), false)
+                    .map_err(|_| eyre::eyre!("there are no puzzles for {year} (set {source})"))?;
+                setting("year", Some((year.to_string(), source)));
+                Some(parsed)
+            }
+            None => {
+                setting("year", None);
+                None
+            }
+        };
+
+        let (cache_dir, source) = layers
+            .find(|layer| layer.cache_dir.clone())
+            .unwrap_or_else(|| (PathBuf::from("cached_input"), Source::Default));
+        setting("cache-dir", Some((quoted(cache_dir.display()), source)));
+
+        let session_file = layers.find(|layer| layer.session_file.clone());
+        setting(
+            "session-file",
+            session_file
+                .as_ref()
+                .map(|(file, source)| (quoted(file.display()), source.clone())),
+        );
+
+        let (format, source) = layers
+            .find(|layer| layer.format)
+            .unwrap_or((Format::Text, Source::Default));
+        setting("format", Some((quoted(format), source)));
+
+        let (timeout, source) = layers
+            .find(|layer| layer.timeout)
+            .unwrap_or((Duration::from_secs(30), Source::Default));
+        setting("timeout", Some((quoted(format!("{timeout:?}")), source)));
+
+        let (port, source) = layers
+            .find(|layer| layer.port)
+            .unwrap_or((8080, Source::Default));
+        setting("port", Some((port.to_string(), source)));
+
+        let (host, source) = layers
+            .find(|layer| layer.host)
+            .unwrap_or((IpAddr::V4(Ipv4Addr::LOCALHOST), Source::Default));
+        setting("host", Some((quoted(host), source)));
+
+        let (speed, source) = layers
+            .find(|layer| layer.speed)
+            .unwrap_or((10.0, Source::Default));
+        visualize::check_speed(speed).map_err(|err| eyre::eyre!("{err} (set {source})"))?;
+        setting("speed", Some((format!("{speed:?}"), source)));
+
+        let (confirm_submit, source) = layers
+            .find(|layer| layer.confirm_submit)
+            .unwrap_or((true, Source::Default));
+        setting("confirm-submit", Some((confirm_submit.to_string(), source)));
+
+        Ok(Self {
+            year,
+            cache_dir,
+            session_file: session_file.map(|(file, _)| file),
+            format,
+            timeout,
+            port,
+            host,
+            speed,
+            confirm_submit,
+            shown,
+        })
+    }
+
+    /// The session cookie for the Advent of Code website
+    pub fn session(&self) -> eyre::Result<String> {
+        match &self.session_file {
+            Some(file) => Ok(fs::read_to_string(file)
+                .wrap_err_with(|| format!("couldn't read {}", file.display()))?
+                .trim()
+                .to_string()),
+            None => Ok(include_str!("../session.txt").trim().to_string()),
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::resolve(&Layers::default()).expect("the defaults are valid")
+    }
+}
+
+/// Prints like an `aoc.toml`, with where each setting came from
+impl Display for Settings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .shown
+            .iter()
+            .map(|(name, value, _)| match value {
+                Some(value) => format!("{name} = {value}"),
+                None => format!("# {name} is not set"),
+            })
+            .collect();
+        let width = lines.iter().map(String::len).max().unwrap_or(0);
+
+        for (line, (_, _, source)) in lines.iter().zip(&self.shown) {
+            writeln!(f, "{line:width$}  # {source}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Makes these the settings that [`get`] returns
+pub fn set(settings: Settings) {
+    let _ = SETTINGS.set(settings);
+}
+
+/// The settings in use, which are the defaults if none have been [`set`]
+pub fn get() -> &'static Settings {
+    SETTINGS.get_or_init(Settings::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_beat_files_beat_defaults() {
+        let file: Layer = toml::from_str(
+            "year = 2021\ncache-dir = \"cache\"\ntimeout = \"5s\"\nconfirm-submit = false",
+        )
+        .unwrap();
+        let flags = Layer {
+            cache_dir: Some(PathBuf::from("flagged")),
+            ..Default::default()
+        };
+        let layers = Layers(vec![(Source::File(PathBuf::from(FILE_NAME)), file)]).with_flags(flags);
+
+        let settings = Settings::resolve(&layers).unwrap();
+        assert!(matches!(settings.year, Some(Year::Y2021)));
+        assert_eq!(settings.cache_dir, PathBuf::from("flagged"));
+        assert_eq!(settings.timeout, Duration::from_secs(5));
+        assert!(!settings.confirm_submit);
+        assert_eq!(settings.port, 8080);
+
+        let shown = settings.to_string();
+        assert!(shown.contains("cache-dir = \"flagged\""));
+        assert!(shown.contains("# from a flag"));
+        assert!(shown.contains("# session-file is not set"));
+    }
+
+    #[test]
+    fn rejects_bad_files() {
+        assert!(toml::from_str::<Layer>("colour = \"red\"").is_err());
+        assert!(toml::from_str::<Layer>("timeout = \"soon\"").is_err());
+
+        let file: Layer = toml::from_str("year = 1999").unwrap();
+        let layers = Layers(vec![(Source::Default, file)]);
+        assert!(Settings::resolve(&layers).is_err());
+
+        for speed in ["0.0", "-1.0", "nan", "inf"] {
+            let file: Layer = toml::from_str(&format!("speed = {speed}")).unwrap();
+            let layers = Layers(vec![(Source::Default, file)]);
+            assert!(Settings::resolve(&layers).is_err());
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Tests generated by `build.rs` from the fixtures in `tests/examples`
+
+use crate::{params::Params, registry, Day, Part, Year};
+
+fn check(year: Year, day: Day, part: Part, input: &str, expected: &str) {
+    let solver = registry::solver(year, day, part)
+        .unwrap_or_else(|| panic!("there is no solution for {year} day {day} {part}"));
+    let answer = solver(input.trim_end_matches(['\r', '\n']), &Params::default())
+        .unwrap_or_else(|err| panic!("{year} day {day} {part} failed: {err:?}"));
+    assert_eq!(answer.to_string(), expected.trim());
+}
+
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
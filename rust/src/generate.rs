@@ -0,0 +1,58 @@
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{registry, Day, Year};
+
+/// Produces random, valid inputs for a day so that solvers can be tried on
+/// inputs much bigger than the real one
+pub trait InputGenerator {
+    /// Generates an input whose size scales with `size` (what that means
+    /// exactly is up to the day, e.g. the number of lines)
+    fn generate(&self, rng: &mut StdRng, size: usize) -> String;
+}
+
+/// Generates an input for a puzzle, always giving the same input for the
+/// same seed
+pub fn generate(year: Year, day: Day, size: usize, seed: u64) -> eyre::Result<String> {
+    let generator = registry::generator(year, day)
+        .ok_or_else(|| eyre::eyre!("There is no input generator for {year} day {day}"))?;
+    let mut rng = StdRng::seed_from_u64(seed);
+    Ok(generator.generate(&mut rng, size))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use super::*;
+    use crate::Part;
+
+    #[test]
+    fn same_seed_same_input() {
+        let first = generate(Year::Y2022, Day::D20, 100, 7).unwrap();
+        let second = generate(Year::Y2022, Day::D20, 100, 7).unwrap();
+        let other = generate(Year::Y2022, Day::D20, 100, 8).unwrap();
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn generated_inputs_can_be_solved() {
+        for &year in Year::value_variants() {
+            for &day in Day::value_variants() {
+                // 2022 day 19 takes minutes per blueprint in debug builds
+                let too_slow = matches!((year, day), (Year::Y2022, Day::D19));
+                if too_slow || registry::generator(year, day).is_none() {
+                    continue;
+                }
+                let input = generate(year, day, 10, 0).unwrap();
+                for &part in Part::value_variants() {
+                    if let Some(solver) = registry::solver(year, day, part) {
+                        if let Err(err) = solver(&input, &Default::default()) {
+                            panic!("{year} day {day} {part} failed on:\n{input}\n{err}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
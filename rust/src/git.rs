@@ -0,0 +1,55 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Finds the hash of the commit that's currently checked out by reading the
+/// repository's files directly, so that we don't have to shell out to `git`
+pub fn head_commit() -> Option<String> {
+    let git_dir = find_git_dir(&std::env::current_dir().ok()?)?;
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    let Some(reference) = head.strip_prefix("ref: ") else {
+        // a detached HEAD contains the hash itself
+        return Some(head.to_string());
+    };
+
+    // worktrees keep their refs in the main repository's git dir
+    let common_dir = match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common_dir) => git_dir.join(common_dir.trim()),
+        Err(_) => git_dir.clone(),
+    };
+
+    for dir in [&git_dir, &common_dir] {
+        if let Ok(hash) = fs::read_to_string(dir.join(reference)) {
+            return Some(hash.trim().to_string());
+        }
+    }
+
+    let packed_refs = fs::read_to_string(common_dir.join("packed-refs")).ok()?;
+    packed_refs.lines().find_map(|line| {
+        let (hash, name) = line.split_once(' ')?;
+        (name == reference).then(|| hash.to_string())
+    })
+}
+
+fn find_git_dir(start: &Path) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        let candidate = dir.join(".git");
+        let Ok(metadata) = fs::metadata(&candidate) else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            return Some(candidate);
+        }
+
+        // in worktrees and submodules `.git` is a file pointing at the real git dir
+        let contents = fs::read_to_string(&candidate).ok()?;
+        let git_dir = contents.trim().strip_prefix("gitdir: ")?;
+        return Some(dir.join(git_dir));
+    }
+
+    None
+}
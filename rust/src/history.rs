@@ -0,0 +1,323 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use eyre::Context;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{cache_dir, git, Day, Part, Year};
+
+/// The name of the history file, which is kept in the cache directory
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// How many of the most recent runs of a puzzle count towards its median
+const RECENT_RUNS: usize = 10;
+/// How many earlier runs a puzzle needs before we'll warn about it slowing down
+const MIN_RUNS_FOR_ALERT: usize = 3;
+/// How many times slower than its recent median a run has to be to get flagged
+const SLOWDOWN_FACTOR: f64 = 1.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Variant {
+    Input,
+    Example,
+}
+
+impl std::fmt::Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Input => write!(f, "input"),
+            Self::Example => write!(f, "example"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Run {
+    year: u16,
+    day: u8,
+    part: u8,
+    variant: Variant,
+    /// A hash of the input, so that runs on different inputs (like each of
+    /// the example files) aren't compared with each other. Runs recorded
+    /// before this was added don't have one.
+    #[serde(default)]
+    input: Option<String>,
+    commit: Option<String>,
+    timestamp: u64,
+    nanos: u64,
+}
+
+impl Run {
+    fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.nanos)
+    }
+
+    /// Whether the two runs are of the same part of a puzzle on the same
+    /// input, which is when their times can be compared
+    fn same_series(&self, other: &Self) -> bool {
+        self.year == other.year
+            && self.day == other.day
+            && self.part == other.part
+            && self.variant == other.variant
+            && self.input == other.input
+    }
+}
+
+/// Tells inputs apart without keeping copies of them. This is FNV-1a rather
+/// than std's hasher, whose output can change between Rust versions.
+fn fingerprint(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+/// A run that took significantly longer than the recent runs of the same puzzle
+#[derive(Debug, Clone, Copy)]
+pub struct Regression {
+    pub median: Duration,
+    pub elapsed: Duration,
+}
+
+impl std::fmt::Display for Regression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "this run took {:?}, {:.1}x the recent median of {:?}",
+            self.elapsed,
+            self.elapsed.as_secs_f64() / self.median.as_secs_f64(),
+            self.median
+        )
+    }
+}
+
+/// Where the history file is (or would be)
+fn history_path() -> eyre::Result<PathBuf> {
+    let mut path = cache_dir()
+        .ok_or_else(|| eyre::eyre!("there's no cache directory to keep the history in"))?;
+    path.push(HISTORY_FILE);
+    Ok(path)
+}
+
+fn load(path: &Path) -> eyre::Result<Vec<Run>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).wrap_err("can't read the run history"),
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .wrap_err_with(|| format!("can't parse line {} of {HISTORY_FILE}", i + 1))
+        })
+        .collect()
+}
+
+fn median(mut durations: Vec<Duration>) -> Option<Duration> {
+    durations.sort_unstable();
+    let mid = durations.len() / 2;
+    match durations.len() {
+        0 => None,
+        len if len.is_multiple_of(2) => Some((durations[mid - 1] + durations[mid]) / 2),
+        _ => Some(durations[mid]),
+    }
+}
+
+/// Returns the median of the most recent of `runs`, if there are enough of them
+/// to be meaningful
+fn recent_median<'a>(runs: impl DoubleEndedIterator<Item = &'a Run>) -> Option<Duration> {
+    let recent = runs
+        .rev()
+        .take(RECENT_RUNS)
+        .map(Run::elapsed)
+        .collect::<Vec<_>>();
+    if recent.len() < MIN_RUNS_FOR_ALERT {
+        return None;
+    }
+    median(recent)
+}
+
+fn is_regression(elapsed: Duration, median: Duration) -> bool {
+    elapsed.as_secs_f64() > median.as_secs_f64() * SLOWDOWN_FACTOR
+}
+
+/// Appends a run to the history file, returning a [`Regression`] if it was
+/// significantly slower than the recent runs of the same puzzle on the same
+/// input
+pub fn record(
+    year: Year,
+    day: Day,
+    part: Part,
+    variant: Variant,
+    input: &str,
+    elapsed: Duration,
+) -> eyre::Result<Option<Regression>> {
+    record_in(&history_path()?, year, day, part, variant, input, elapsed)
+}
+
+fn record_in(
+    path: &Path,
+    year: Year,
+    day: Day,
+    part: Part,
+    variant: Variant,
+    input: &str,
+    elapsed: Duration,
+) -> eyre::Result<Option<Regression>> {
+    let run = Run {
+        year: year.number(),
+        day: day.number(),
+        part: part.number(),
+        variant,
+        input: Some(fingerprint(input)),
+        commit: git::head_commit(),
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or(0),
+        nanos: elapsed.as_nanos().try_into()?,
+    };
+
+    let history = load(path)?;
+    let median = recent_median(history.iter().filter(|other| other.same_series(&run)));
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .wrap_err("can't open the run history")?;
+    writeln!(file, "{}", serde_json::to_string(&run)?)?;
+
+    Ok(median
+        .filter(|&median| is_regression(elapsed, median))
+        .map(|median| Regression { median, elapsed }))
+}
+
+/// Prints how the solve times for each part of a puzzle on each input have
+/// changed over time
+pub fn print(year: Year, day: Day) -> eyre::Result<()> {
+    write_trend(&history_path()?, year, day, &mut io::stdout().lock())
+}
+
+fn write_trend(path: &Path, year: Year, day: Day, out: &mut impl Write) -> eyre::Result<()> {
+    let history = load(path)?;
+
+    let mut printed_any = false;
+    for part in [Part::Part1, Part::Part2] {
+        for variant in [Variant::Input, Variant::Example] {
+            let runs = history
+                .iter()
+                .filter(|run| {
+                    run.year == year.number()
+                        && run.day == day.number()
+                        && run.part == part.number()
+                        && run.variant == variant
+                })
+                .collect::<Vec<_>>();
+            for first in runs.iter().unique_by(|run| &run.input) {
+                let runs = runs
+                    .iter()
+                    .copied()
+                    .filter(|run| run.same_series(first))
+                    .collect::<Vec<_>>();
+                match &first.input {
+                    Some(input) => {
+                        let input = &input[..input.len().min(8)];
+                        writeln!(out, "{year} day {day} {part} ({variant} {input}):")?
+                    }
+                    None => writeln!(out, "{year} day {day} {part} ({variant}):")?,
+                }
+                for (i, run) in runs.iter().enumerate() {
+                    let commit = match &run.commit {
+                        Some(commit) => &commit[..commit.len().min(8)],
+                        None => "unknown",
+                    };
+                    let elapsed = format!("{:?}", run.elapsed());
+                    let trend = match recent_median(runs[..i].iter().copied()) {
+                        Some(median) => {
+                            let change = run.elapsed().as_secs_f64() / median.as_secs_f64() - 1.0;
+                            let flag = if is_regression(run.elapsed(), median) {
+                                "  <- regression"
+                            } else {
+                                ""
+                            };
+                            format!("{:+.1}% vs median{flag}", change * 100.0)
+                        }
+                        None => String::new(),
+                    };
+                    writeln!(out, "  {commit:<8}  {elapsed:>12}  {trend}")?;
+                }
+                printed_any = true;
+            }
+        }
+    }
+
+    if !printed_any {
+        writeln!(out, "There are no recorded runs for {year} day {day}")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_and_prints_runs() {
+        let path = std::env::temp_dir().join(format!("aoc-history-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let run = |input, millis| {
+            let elapsed = Duration::from_millis(millis);
+            record_in(
+                &path,
+                Year::Y2022,
+                Day::D20,
+                Part::Part1,
+                Variant::Example,
+                input,
+                elapsed,
+            )
+        };
+
+        for _ in 0..MIN_RUNS_FOR_ALERT {
+            assert!(run("1\n2\n-3", 10).unwrap().is_none());
+        }
+        // a bigger example is slower, but isn't compared with the small one
+        assert!(run("1\n2\n-3\n3\n-2\n0\n4", 100).unwrap().is_none());
+        let regression = run("1\n2\n-3", 100).unwrap().unwrap();
+        assert_eq!(regression.median, Duration::from_millis(10));
+
+        let mut printed = Vec::new();
+        write_trend(&path, Year::Y2022, Day::D20, &mut printed).unwrap();
+        let mut other_day = Vec::new();
+        write_trend(&path, Year::Y2022, Day::D19, &mut other_day).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let printed = String::from_utf8(printed).unwrap();
+        let lines: Vec<&str> = printed.lines().collect();
+        let small = &fingerprint("1\n2\n-3")[..8];
+        let big = &fingerprint("1\n2\n-3\n3\n-2\n0\n4")[..8];
+        assert_eq!(lines[0], format!("2022 day 20 part 1 (example {small}):"));
+        assert!(lines[MIN_RUNS_FOR_ALERT + 1].ends_with("+900.0% vs median  <- regression"));
+        assert_eq!(
+            lines[MIN_RUNS_FOR_ALERT + 2],
+            format!("2022 day 20 part 1 (example {big}):")
+        );
+        assert_eq!(lines.len(), MIN_RUNS_FOR_ALERT + 4);
+        assert_eq!(
+            String::from_utf8(other_day).unwrap(),
+            "There are no recorded runs for 2022 day 19\n"
+        );
+    }
+}
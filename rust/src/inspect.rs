@@ -0,0 +1,159 @@
+use std::{collections::BTreeSet, fmt::Display, ops::RangeInclusive};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static NUMBER: Lazy<Regex> = Lazy::new(|| Regex::new(r"-?\d+").unwrap());
+
+/// Facts about a group of lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lines {
+    pub count: usize,
+    /// The shortest and longest line, in characters
+    pub lengths: RangeInclusive<usize>,
+    pub distinct_lengths: usize,
+}
+
+impl Lines {
+    fn new<'a>(lines: impl IntoIterator<Item = &'a str>) -> Self {
+        let lengths: Vec<usize> = lines.into_iter().map(|line| line.chars().count()).collect();
+        let min = lengths.iter().copied().min().unwrap_or(0);
+        let max = lengths.iter().copied().max().unwrap_or(0);
+        Self {
+            count: lengths.len(),
+            lengths: min..=max,
+            distinct_lengths: lengths.iter().collect::<BTreeSet<_>>().len(),
+        }
+    }
+
+    /// The width and height if every line is the same length, and there's
+    /// more than one of them
+    pub fn grid(&self) -> Option<(usize, usize)> {
+        (self.count > 1 && self.distinct_lengths == 1 && *self.lengths.start() > 1)
+            .then_some((*self.lengths.start(), self.count))
+    }
+}
+
+impl Display for Lines {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let plural = if self.count == 1 { "" } else { "s" };
+        write!(f, "{} line{plural}, ", self.count)?;
+        if self.lengths.start() == self.lengths.end() {
+            write!(f, "{} characters long", self.lengths.start())?;
+        } else {
+            write!(
+                f,
+                "{} to {} characters long ({} different lengths)",
+                self.lengths.start(),
+                self.lengths.end(),
+                self.distinct_lengths
+            )?;
+        }
+        if let Some((width, height)) = self.grid() {
+            write!(f, ", a {width}x{height} grid")?;
+        }
+        Ok(())
+    }
+}
+
+/// Quick facts about an input, to check assumptions before writing a parser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub lines: Lines,
+    pub chars: BTreeSet<char>,
+    pub numbers: Option<RangeInclusive<i128>>,
+    pub number_count: usize,
+    /// The groups of lines separated by blank lines
+    pub blocks: Vec<Lines>,
+}
+
+impl Report {
+    pub fn new(input: &str) -> Self {
+        let lines: Vec<&str> = input
+            .lines()
+            .map(|line| line.trim_end_matches('\r'))
+            .collect();
+
+        let blocks = lines
+            .split(|line| line.is_empty())
+            .filter(|block| !block.is_empty())
+            .map(|block| Lines::new(block.iter().copied()))
+            .collect();
+
+        let numbers: Vec<i128> = NUMBER
+            .find_iter(input)
+            .filter_map(|number| number.as_str().parse().ok())
+            .collect();
+        let number_range = numbers
+            .iter()
+            .copied()
+            .min()
+            .zip(numbers.iter().copied().max());
+
+        Self {
+            lines: Lines::new(lines.iter().copied()),
+            chars: lines.iter().flat_map(|line| line.chars()).collect(),
+            numbers: number_range.map(|(min, max)| min..=max),
+            number_count: numbers.len(),
+            blocks,
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "lines:      {}", self.lines)?;
+
+        let chars: String = self.chars.iter().collect();
+        writeln!(f, "characters: {chars:?} ({} different)", self.chars.len())?;
+
+        match &self.numbers {
+            Some(range) => writeln!(
+                f,
+                "numbers:    {} from {} to {}",
+                self.number_count,
+                range.start(),
+                range.end()
+            )?,
+            None => writeln!(f, "numbers:    none")?,
+        }
+
+        write!(f, "blocks:     {}", self.blocks.len())?;
+        if self.blocks.len() > 1 {
+            for (i, block) in self.blocks.iter().enumerate() {
+                write!(f, "\n  {}: {block}", i + 1)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_grids() {
+        let report = Report::new("#.#\n...\r\n#.#\n");
+        assert_eq!(report.lines.grid(), Some((3, 3)));
+        assert_eq!(report.chars, BTreeSet::from(['#', '.']));
+        assert_eq!(report.numbers, None);
+        assert_eq!(report.blocks.len(), 1);
+
+        let report = Report::new("  ..#\n.#\n..\n\n10R5L5\n");
+        assert_eq!(report.lines.grid(), None);
+        assert_eq!(report.lines.lengths, 0..=6);
+        assert_eq!(report.lines.distinct_lengths, 4);
+        assert_eq!(report.blocks[0].lengths, 2..=5);
+        assert_eq!(report.blocks[1].grid(), None);
+    }
+
+    #[test]
+    fn finds_numbers_and_blocks() {
+        let report = Report::new("1\n-20\n\n\n300,4\n\n5");
+        assert_eq!(report.numbers, Some(-20..=300));
+        assert_eq!(report.number_count, 5);
+        assert_eq!(report.blocks.len(), 3);
+        assert_eq!(report.blocks[1].count, 1);
+    }
+}
@@ -1,12 +1,37 @@
 use std::{
+    ffi::OsString,
     fs::{self, File},
     io::{ErrorKind, Read, Write},
+    net::IpAddr,
     path::PathBuf,
+    time::Duration,
 };
 
-use clap::Parser;
+use clap::{
+    error::{ContextKind, ContextValue},
+    CommandFactory, FromArgMatches, Parser, ValueEnum,
+};
+use log::LevelFilter;
 use reqwest::{blocking::Client, header::COOKIE};
 
+mod answer;
+mod completions;
+mod config;
+#[cfg(test)]
+mod examples;
+mod generate;
+mod git;
+mod history;
+mod inspect;
+mod params;
+mod puzzle;
+mod registry;
+mod repl;
+mod runner;
+mod serve;
+mod util;
+mod visualize;
+mod watch;
 mod y2021;
 mod y2022;
 
@@ -29,6 +54,15 @@ impl std::fmt::Display for Year {
     }
 }
 
+impl Year {
+    fn number(self) -> u16 {
+        match self {
+            Self::Y2021 => 2021,
+            Self::Y2022 => 2022,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, clap::ValueEnum)]
 #[repr(u8)]
 enum Day {
@@ -140,6 +174,12 @@ impl std::fmt::Display for Day {
     }
 }
 
+impl Day {
+    fn number(self) -> u8 {
+        self as u8 + 1
+    }
+}
+
 #[derive(Debug, Clone, Copy, clap::ValueEnum)]
 #[repr(u8)]
 enum Part {
@@ -159,10 +199,133 @@ impl std::fmt::Display for Part {
     }
 }
 
+impl Part {
+    fn number(self) -> u8 {
+        self as u8 + 1
+    }
+}
+
 #[derive(clap::Parser)]
+#[command(
+    subcommand_negates_reqs = true,
+    allow_missing_positional = true,
+    override_usage = "aoc [OPTIONS] [YEAR] <DAY> <PART>\n       aoc [OPTIONS] <COMMAND>"
+)]
 struct Args {
-    /// The year to solve
-    year: Year,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    solve: Option<SolveArgs>,
+
+    /// Print debugging output to stderr (`-vv` for even more); `AOC_LOG` can
+    /// also be set to an env_logger filter like `aoc::y2022=debug`
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Where to cache downloaded inputs and puzzles [default: cached_input]
+    #[arg(long, global = true)]
+    cache_dir: Option<PathBuf>,
+
+    /// A file to read the session cookie from, instead of the one built in
+    #[arg(long, global = true)]
+    session_file: Option<PathBuf>,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Show how the solve times for a puzzle have changed over time
+    History {
+        /// The year of the puzzle
+        year: Year,
+
+        /// The day of the puzzle
+        day: Day,
+    },
+
+    /// Print the description of a puzzle
+    Read {
+        /// The year of the puzzle
+        year: Year,
+
+        /// The day of the puzzle
+        day: Day,
+    },
+
+    /// Print some facts about a puzzle's input, to check before parsing it
+    Inspect {
+        /// The year of the puzzle
+        year: Year,
+
+        /// The day of the puzzle
+        day: Day,
+
+        /// Example data to inspect (if left blank, use the actual puzzle input)
+        #[arg(short, long)]
+        example_data: Option<String>,
+    },
+
+    /// Print a randomly generated input for a puzzle
+    Gen {
+        /// The year of the puzzle
+        year: Year,
+
+        /// The day of the puzzle
+        day: Day,
+
+        /// How big of an input to generate (e.g. the number of lines)
+        #[arg(long)]
+        size: usize,
+
+        /// The seed to generate the input from
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+
+    /// Serve the solutions as a JSON API over HTTP
+    Serve {
+        /// The port to listen on [default: 8080]
+        #[arg(long)]
+        port: Option<u16>,
+
+        /// The address to listen on [default: 127.0.0.1]
+        #[arg(long)]
+        host: Option<IpAddr>,
+
+        /// How long to wait for a solve before giving up on it [default: 30s]
+        #[arg(long, value_parser = runner::parse_duration)]
+        timeout: Option<Duration>,
+    },
+
+    /// Start an interactive prompt that keeps a puzzle's input loaded
+    Repl,
+
+    /// Print a completion script for a shell
+    Completions {
+        /// The shell to complete in
+        shell: clap_complete::Shell,
+    },
+
+    /// Print a man page
+    Man,
+
+    /// Work with the settings from `aoc.toml` files and the environment
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum ConfigCommand {
+    /// Print the settings in effect and where each one came from
+    Show,
+}
+
+#[derive(clap::Args)]
+struct SolveArgs {
+    /// The year to solve (if left out, use the default year from the config)
+    year: Option<Year>,
 
     /// The day to solve
     day: Day,
@@ -174,104 +337,432 @@ struct Args {
     /// Example data to use (if left blank, use the actual puzzle input)
     #[arg(short, long)]
     example_data: Option<String>,
+
+    /// A file to read example data from
+    #[arg(short = 'f', long, conflicts_with = "example_data")]
+    example_file: Option<PathBuf>,
+
+    /// Solve the puzzle this many times and print only the aggregate timing
+    /// (for profiling)
+    #[arg(long, conflicts_with = "duration")]
+    repeat: Option<u64>,
+
+    /// Keep solving the puzzle for this long (e.g. `10s`) and print only the
+    /// aggregate timing (for profiling)
+    #[arg(long, value_parser = runner::parse_duration)]
+    duration: Option<Duration>,
+
+    /// Step through the puzzle's simulation in the terminal instead of solving it
+    #[arg(long, conflicts_with_all = ["repeat", "duration"])]
+    visualize: bool,
+
+    /// How many simulation steps to show per second when visualizing
+    /// [default: 10]
+    #[arg(long, requires = "visualize", value_parser = visualize::parse_speed)]
+    speed: Option<f64>,
+
+    /// How to print the answer [default: text]
+    #[arg(long, value_enum)]
+    format: Option<config::Format>,
+
+    /// Solve the puzzle again every time the input (or example file) changes
+    #[arg(long, conflicts_with_all = ["example_data", "repeat", "duration", "visualize"])]
+    watch: bool,
+
+    /// Change one of the numbers the solver takes from the puzzle statement,
+    /// e.g. `--param rounds=20` (can be given more than once)
+    #[arg(long = "param", value_name = "KEY=VALUE", value_parser = params::parse_param)]
+    params: Vec<(String, i64)>,
 }
 
-fn fetch_input(day: Day, year: Year) -> eyre::Result<String> {
+/// Finds (creating it if need be) the directory that downloads are cached in
+fn cache_dir() -> Option<PathBuf> {
+    let cache_dir = &config::get().cache_dir;
     let mut dir = None;
-    match fs::metadata("cached_input") {
+    match fs::metadata(cache_dir) {
         Ok(metadata) => {
             if metadata.is_dir() {
-                dir = Some(PathBuf::from("cached_input"));
+                dir = Some(cache_dir.clone());
             }
         }
         Err(err) => {
             if let ErrorKind::NotFound = err.kind() {
-                if let Ok(()) = std::fs::create_dir("cached_input") {
-                    dir = Some(PathBuf::from("cached_input"))
+                if let Ok(()) = std::fs::create_dir_all(cache_dir) {
+                    dir = Some(cache_dir.clone())
                 }
             }
         }
     }
+    dir
+}
+
+/// Downloads a page from the Advent of Code website using our session
+fn fetch(path: &str) -> eyre::Result<String> {
+    let session = config::get().session()?;
+    let client = Client::new();
+    let request = client
+        .get(format!("https://adventofcode.com/{path}"))
+        .header(COOKIE, format!("session={session}"))
+        .build()?;
+    Ok(client.execute(request)?.error_for_status()?.text()?)
+}
+
+/// Where a puzzle's input is (or would be) cached
+fn cached_input_path(day: Day, year: Year) -> Option<PathBuf> {
+    let mut path = cache_dir()?;
+    path.push(format!("y{year}d{day}.txt"));
+    Some(path)
+}
 
-    let mut file = None;
-    if let Some(mut path) = dir {
-        path.push(format!("y{year}d{day}.txt"));
+fn fetch_input(day: Day, year: Year) -> eyre::Result<String> {
+    let mut cache_path = None;
+    if let Some(path) = cached_input_path(day, year) {
         match fs::metadata(&path) {
             Ok(metadata) => {
                 if metadata.is_file() {
                     if let Ok(mut opened_file) = File::open(&path) {
                         let mut input = String::new();
                         if let Ok(_) = opened_file.read_to_string(&mut input) {
+                            log::debug!("using the cached input in {}", path.display());
                             return Ok(input);
                         }
                     }
                 }
             }
             Err(err) => {
+                // only create the file once there's something to put in it, so
+                // that a failed download doesn't leave an empty input behind
                 if let ErrorKind::NotFound = err.kind() {
-                    if let Ok(opened_file) = File::create(&path) {
-                        file = Some(opened_file);
-                    }
+                    cache_path = Some(path);
                 }
             }
         }
     }
 
-    const SESSION: &str = concat!("session=", include_str!("../session.txt"));
-    let client = Client::new();
-    let request = client
-        .get(format!("https://adventofcode.com/{year}/day/{day}/input"))
-        .header(COOKIE, SESSION)
-        .build()?;
-    let mut input = client.execute(request)?.text()?;
+    log::debug!("downloading the input for {year} day {day}");
+    let mut input = fetch(&format!("{year}/day/{day}/input"))?;
 
     while input.ends_with(&['\r', '\n']) {
         let _ = input.pop();
     }
 
-    if let Some(mut file) = file {
-        let _ = file.write_all(input.as_bytes());
+    if let Some(path) = cache_path {
+        if let Ok(mut file) = File::create(path) {
+            let _ = file.write_all(input.as_bytes());
+        }
     }
 
     Ok(input)
 }
 
+/// Sends log output to stderr, only showing warnings unless asked for more
+fn init_logging(verbose: u8) {
+    let mut builder = env_logger::Builder::new();
+    builder
+        .filter_level(LevelFilter::Warn)
+        .parse_env("AOC_LOG")
+        .format_timestamp(None)
+        .target(env_logger::Target::Stderr);
+    match verbose {
+        0 => {}
+        1 => {
+            builder.filter_module("aoc", LevelFilter::Debug);
+        }
+        _ => {
+            builder.filter_module("aoc", LevelFilter::Trace);
+        }
+    }
+    builder.init();
+}
+
+/// Parses the command line, where the year can be left out, and either a
+/// puzzle or a command can be given but not both
+fn parse_args(args: &[OsString]) -> Result<Args, clap::Error> {
+    let parsed = match Args::try_parse_from(args) {
+        Ok(parsed) => parsed,
+        Err(err) => parse_args_without_year(args, err)?,
+    };
+    if parsed.command.is_some() && parsed.solve.is_some() {
+        return Err(Args::command().error(
+            clap::error::ErrorKind::ArgumentConflict,
+            "a puzzle can't be given along with a command",
+        ));
+    }
+    Ok(parsed)
+}
+
+/// Clap fills in positionals from the left, so if the first one turns out to
+/// be a day rather than a year, it's parsed again with the year only as a flag
+fn parse_args_without_year(args: &[OsString], err: clap::Error) -> Result<Args, clap::Error> {
+    let context = |kind| match err.get(kind) {
+        Some(ContextValue::String(context)) => Some(context.as_str()),
+        _ => None,
+    };
+    let given_a_day = context(ContextKind::InvalidArg) == Some("[YEAR]")
+        && context(ContextKind::InvalidValue)
+            .is_some_and(|value| Day::from_str(value, false).is_ok());
+    if !given_a_day {
+        return Err(err);
+    }
+    let without_year = Args::command().mut_arg("year", |year| year.long("year"));
+    Args::from_arg_matches(&without_year.try_get_matches_from(args)?)
+}
+
+impl Args {
+    /// The settings given on the command line, which beat every other source
+    fn flags(&self) -> config::Layer {
+        let mut flags = config::Layer {
+            cache_dir: self.cache_dir.clone(),
+            session_file: self.session_file.clone(),
+            ..Default::default()
+        };
+        if let Some(Command::Serve {
+            port,
+            host,
+            timeout,
+        }) = self.command
+        {
+            flags.port = port;
+            flags.host = host;
+            flags.timeout = timeout;
+        }
+        if let Some(solve) = &self.solve {
+            flags.year = solve.year.map(Year::number);
+            flags.format = solve.format;
+            flags.speed = solve.speed;
+        }
+        flags
+    }
+}
+
 fn main() -> eyre::Result<()> {
+    let args =
+        parse_args(&std::env::args_os().collect::<Vec<_>>()).unwrap_or_else(|err| err.exit());
+    let flags = args.flags();
     let Args {
+        command,
+        solve,
+        verbose,
+        ..
+    } = args;
+    init_logging(verbose);
+    // these have to work even when the config is broken
+    let needs_config = !matches!(
+        command,
+        Some(Command::Completions { .. } | Command::Man | Command::Config { .. })
+    );
+    if needs_config {
+        let layers = config::Layers::load()?.with_flags(flags.clone());
+        config::set(config::Settings::resolve(&layers)?);
+    }
+    let settings = config::get();
+
+    match (command, solve) {
+        (Some(Command::History { year, day }), _) => history::print(year, day),
+        (Some(Command::Read { year, day }), _) => puzzle::read(year, day),
+        (
+            Some(Command::Inspect {
+                year,
+                day,
+                example_data,
+            }),
+            _,
+        ) => {
+            let input = match example_data {
+                Some(example_data) => example_data,
+                None => fetch_input(day, year)?,
+            };
+            println!("{}", inspect::Report::new(&input));
+            Ok(())
+        }
+        (
+            Some(Command::Gen {
+                year,
+                day,
+                size,
+                seed,
+            }),
+            _,
+        ) => {
+            println!("{}", generate::generate(year, day, size, seed)?);
+            Ok(())
+        }
+        (Some(Command::Serve { .. }), _) => {
+            serve::serve(settings.host, settings.port, settings.timeout)
+        }
+        (Some(Command::Repl), _) => repl::repl(),
+        (Some(Command::Completions { shell }), _) => {
+            completions::completions(shell);
+            Ok(())
+        }
+        (Some(Command::Man), _) => completions::man(),
+        (
+            Some(Command::Config {
+                command: ConfigCommand::Show,
+            }),
+            _,
+        ) => {
+            let files = config::files();
+            if files.is_empty() {
+                println!("# no aoc.toml was found");
+            }
+            for file in files {
+                println!("# reading {}", file.display());
+            }
+            let (layers, errors) = config::Layers::load_all();
+            for (source, err) in &errors {
+                println!("# ignoring the settings {source}: {err:#}");
+            }
+            let resolved = match config::Settings::resolve(&layers.with_flags(flags)) {
+                Ok(settings) => {
+                    print!("{settings}");
+                    true
+                }
+                Err(err) => {
+                    println!("# {err:#}");
+                    false
+                }
+            };
+            eyre::ensure!(
+                errors.is_empty() && resolved,
+                "the config is broken (see above)"
+            );
+            Ok(())
+        }
+        (None, Some(solve_args)) => run(solve_args),
+        (None, None) => eyre::bail!("no puzzle was given"),
+    }
+}
+
+fn run(
+    SolveArgs {
         day,
-        year,
         part,
         example_data,
-    } = Args::try_parse()?;
+        example_file,
+        repeat,
+        duration,
+        visualize,
+        watch,
+        params,
+        ..
+    }: SolveArgs,
+) -> eyre::Result<()> {
+    let settings = config::get();
+    let year = settings
+        .year
+        .ok_or_else(|| eyre::eyre!("No year was given, and there's no default year set"))?;
+    let solver = registry::solver(year, day, part)
+        .ok_or_else(|| eyre::eyre!("There is not yet a solution for that puzzle"))?;
+    let params = params::Params::new(params);
+    params.check(registry::params(year, day, part))?;
+
+    if watch {
+        let path = match example_file {
+            Some(example_file) => example_file,
+            None => {
+                // make sure there's a cached copy to watch
+                fetch_input(day, year)?;
+                cached_input_path(day, year)
+                    .ok_or_else(|| eyre::eyre!("The input couldn't be cached to watch it"))?
+            }
+        };
+        return watch::watch(&path, solver, &params, &format!("{year} day {day} {part}"));
+    }
 
-    let input = match example_data {
-        Some(example_data) => example_data,
-        None => fetch_input(day, year)?,
+    let (input, variant) = match (example_data, example_file) {
+        (Some(example_data), _) => (example_data, history::Variant::Example),
+        (None, Some(example_file)) => (
+            watch::trim_input(&fs::read_to_string(example_file)?).to_string(),
+            history::Variant::Example,
+        ),
+        (None, None) => (fetch_input(day, year)?, history::Variant::Input),
     };
 
-    let answer: Box<dyn std::fmt::Display> = match (year, day, part) {
-        (Year::Y2021, Day::D19, Part::Part1) => Box::new(y2021::d19::p1::solve(&input)?),
-        (Year::Y2021, Day::D19, Part::Part2) => Box::new(y2021::d19::p2::solve(&input)?),
-        (Year::Y2022, Day::D17, Part::Part1) => Box::new(y2022::d17::p1::solve(&input)?),
-        (Year::Y2022, Day::D17, Part::Part2) => Box::new(y2022::d17::p2::solve(&input)?),
-        (Year::Y2022, Day::D18, Part::Part1) => Box::new(y2022::d18::p1::solve(&input)?),
-        (Year::Y2022, Day::D18, Part::Part2) => Box::new(y2022::d18::p2::solve(&input)?),
-        (Year::Y2022, Day::D19, Part::Part1) => Box::new(y2022::d19::p1::solve(&input)?),
-        (Year::Y2022, Day::D19, Part::Part2) => Box::new(y2022::d19::p2::solve(&input)?),
-        (Year::Y2022, Day::D20, Part::Part1) => Box::new(y2022::d20::p1::solve(&input)?),
-        (Year::Y2022, Day::D20, Part::Part2) => Box::new(y2022::d20::p2::solve(&input)?),
-        (Year::Y2022, Day::D21, Part::Part1) => Box::new(y2022::d21::p1::solve(&input)?),
-        (Year::Y2022, Day::D21, Part::Part2) => Box::new(y2022::d21::p2::solve(&input)?),
-        (Year::Y2022, Day::D22, Part::Part1) => Box::new(y2022::d22::p1::solve(&input)?),
-        (Year::Y2022, Day::D22, Part::Part2) => Box::new(y2022::d22::p2::solve(&input)?),
-        (Year::Y2022, Day::D23, Part::Part1) => Box::new(y2022::d23::p1::solve(&input)?),
-        (Year::Y2022, Day::D23, Part::Part2) => Box::new(y2022::d23::p2::solve(&input)?),
-        (Year::Y2022, Day::D24, Part::Part1) => Box::new(y2022::d24::p1::solve(&input)?),
-        (Year::Y2022, Day::D24, Part::Part2) => Box::new(y2022::d24::p2::solve(&input)?),
-        (Year::Y2022, Day::D25, Part::Part1) => Box::new(y2022::d25::p1::solve(&input)),
-        _ => eyre::bail!("There is not yet a solution for that puzzle"),
+    if visualize {
+        let visualizer = registry::visualizer(year, day, part)
+            .ok_or_else(|| eyre::eyre!("There is no visualization for that puzzle"))?;
+        return visualize::play(visualizer(&input, &params)?.as_mut(), settings.speed);
+    }
+
+    let repeat_limit = match (repeat, duration) {
+        (Some(times), _) => Some(runner::RepeatLimit::Times(times)),
+        (None, Some(duration)) => Some(runner::RepeatLimit::For(duration)),
+        (None, None) => None,
     };
-    println!("The solution for {year} day {day} {part} is {answer}");
+    if let Some(limit) = repeat_limit {
+        let stats = runner::repeat(solver, &input, &params, limit)?;
+        println!("Solved {year} day {day} {part}: {stats}");
+        return Ok(());
+    }
+
+    let (answer, elapsed) = runner::time(solver, &input, &params)?;
+    let solution = answer::Solution::new(year, day, part, answer, elapsed);
+    match settings.format {
+        config::Format::Text => println!("{solution}"),
+        config::Format::Json => println!("{}", serde_json::to_string(&solution)?),
+    }
+
+    match history::record(year, day, part, variant, &input, elapsed) {
+        Ok(Some(regression)) => {
+            eprintln!("warning: {year} day {day} {part} got slower: {regression}")
+        }
+        Ok(None) => {}
+        Err(err) => eprintln!("warning: couldn't record this run in the history: {err}"),
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Args, clap::Error> {
+        let args: Vec<OsString> = args.split_whitespace().map(OsString::from).collect();
+        parse_args(&args)
+    }
+
+    fn puzzle(args: &str) -> (Option<u16>, u8, u8) {
+        let solve = parse(args).unwrap().solve.unwrap();
+        (
+            solve.year.map(Year::number),
+            solve.day.number(),
+            solve.part.number(),
+        )
+    }
+
+    #[test]
+    fn the_year_can_be_left_out() {
+        assert_eq!(puzzle("aoc 2021 17 p1"), (Some(2021), 17, 1));
+        assert_eq!(puzzle("aoc 17 p2"), (None, 17, 2));
+        assert_eq!(puzzle("aoc --repeat 3 17 p1"), (None, 17, 1));
+        assert!(parse("aoc 1999 17 p1").is_err());
+        assert!(parse("aoc 17 p3").is_err());
+    }
+
+    #[test]
+    fn flags_can_come_before_a_command() {
+        let args = parse("aoc -v --cache-dir here config show").unwrap();
+        assert!(matches!(args.command, Some(Command::Config { .. })));
+        assert!(args.solve.is_none());
+        assert!(parse("aoc 2022 17 p1 serve").is_err());
+        assert!(parse("aoc 17 p1 repl").is_err());
+    }
+
+    #[test]
+    fn every_setting_flag_is_a_flag_layer() {
+        let flags = parse("aoc --cache-dir here 2021 17 p1 --visualize --speed 2 --format json")
+            .unwrap()
+            .flags();
+        assert_eq!(flags.cache_dir, Some(PathBuf::from("here")));
+        assert_eq!(flags.year, Some(2021));
+        assert_eq!(flags.speed, Some(2.0));
+        assert_eq!(flags.format, Some(config::Format::Json));
+
+        let flags = parse("aoc serve --port 80 --timeout 5s").unwrap().flags();
+        assert_eq!(flags.port, Some(80));
+        assert_eq!(flags.timeout, Some(Duration::from_secs(5)));
+        assert_eq!(flags.host, None);
+    }
+}
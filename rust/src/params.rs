@@ -0,0 +1,100 @@
+use std::{collections::HashMap, fmt::Display};
+
+/// A number baked into a puzzle's statement that its solver lets you change,
+/// e.g. how many rounds to simulate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    /// The value the puzzle actually asks for
+    pub default: i64,
+    pub help: &'static str,
+}
+
+/// Prints like a `--param` flag, along with what it's for
+impl Display for Param {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={} ({})", self.name, self.default, self.help)
+    }
+}
+
+/// The values given for a solver's parameters, by name
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(HashMap<String, i64>);
+
+impl Params {
+    pub fn new(values: impl IntoIterator<Item = (String, i64)>) -> Self {
+        Self(values.into_iter().collect())
+    }
+
+    /// The value given for a parameter, or its default
+    pub fn get<T: TryFrom<i64>>(&self, param: &Param) -> eyre::Result<T> {
+        let value = self.0.get(param.name).copied().unwrap_or(param.default);
+        T::try_from(value).map_err(|_| eyre::eyre!("{value} is out of range for `{}`", param.name))
+    }
+
+    /// Makes sure that every value given is for one of `declared`, listing
+    /// them with their defaults if not
+    pub fn check(&self, declared: &[Param]) -> eyre::Result<()> {
+        for name in self.0.keys() {
+            if !declared.iter().any(|param| param.name == name) {
+                if declared.is_empty() {
+                    eyre::bail!("`{name}` isn't a parameter, this solver doesn't have any");
+                }
+                let list: String = declared
+                    .iter()
+                    .map(|param| format!("\n  {param}"))
+                    .collect();
+                eyre::bail!("`{name}` isn't a parameter, the parameters are:{list}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses a `key=value` pair, where the value can have `_`s in it like
+/// `1_000_000_000_000`
+pub fn parse_param(param: &str) -> Result<(String, i64), String> {
+    let (name, value) = param
+        .split_once('=')
+        .ok_or_else(|| format!("expected `key=value`, got `{param}`"))?;
+    Ok((name.trim().to_string(), parse_value(value)?))
+}
+
+/// Parses a parameter's value, which can have `_`s in it
+pub fn parse_value(value: &str) -> Result<i64, String> {
+    value
+        .replace('_', "")
+        .parse()
+        .map_err(|err| format!("can't parse `{value}` as a number: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROUNDS: Param = Param {
+        name: "rounds",
+        default: 10,
+        help: "how many rounds to play",
+    };
+
+    #[test]
+    fn uses_defaults_unless_given() {
+        assert_eq!(Params::default().get::<usize>(&ROUNDS).unwrap(), 10);
+
+        let params = Params::new([parse_param("rounds=1_000").unwrap()]);
+        assert_eq!(params.get::<usize>(&ROUNDS).unwrap(), 1000);
+        assert!(params.check(&[ROUNDS]).is_ok());
+        assert!(params.check(&[]).is_err());
+
+        let params = Params::new([parse_param("turns=5").unwrap()]);
+        assert_eq!(
+            params.check(&[ROUNDS]).unwrap_err().to_string(),
+            "`turns` isn't a parameter, the parameters are:\n  rounds=10 (how many rounds to play)"
+        );
+
+        let params = Params::new([parse_param("rounds=-1").unwrap()]);
+        assert!(params.get::<usize>(&ROUNDS).is_err());
+        assert!(parse_param("rounds").is_err());
+    }
+}
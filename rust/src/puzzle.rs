@@ -0,0 +1,338 @@
+use std::{fs, io::IsTerminal};
+
+use crate::{cache_dir, fetch, Day, Year};
+
+/// How wide paragraphs get wrapped to
+const WIDTH: usize = 80;
+
+const BOLD: &str = "\x1b[1m";
+const NOT_BOLD: &str = "\x1b[22m";
+const CYAN: &str = "\x1b[36m";
+const DEFAULT_COLOR: &str = "\x1b[39m";
+
+/// Part 2 only shows up on the page once part 1 has been solved
+fn has_part_2(html: &str) -> bool {
+    html.matches("<article").count() >= 2
+}
+
+/// Gets the puzzle's page, from the cache if we can. A cached page that's
+/// missing part 2 gets downloaded again in case it's been unlocked since.
+fn fetch_page(year: Year, day: Day) -> eyre::Result<String> {
+    let path = cache_dir().map(|mut path| {
+        path.push(format!("y{year}d{day}.html"));
+        path
+    });
+    let cached = path.as_ref().and_then(|path| fs::read_to_string(path).ok());
+
+    if let Some(cached) = &cached {
+        if has_part_2(cached) {
+            return Ok(cached.clone());
+        }
+    }
+
+    match fetch(&format!("{year}/day/{day}")) {
+        Ok(html) => {
+            if let Some(path) = path {
+                let _ = fs::write(path, &html);
+            }
+            Ok(html)
+        }
+        // being offline shouldn't stop us from showing what we already have
+        Err(err) => cached.ok_or(err),
+    }
+}
+
+/// Prints the description of a puzzle
+pub fn read(year: Year, day: Day) -> eyre::Result<()> {
+    let html = fetch_page(year, day)?;
+    print!("{}", render(&html, std::io::stdout().is_terminal()));
+    Ok(())
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .map(|end| &rest[1..end])
+            .and_then(|name| match name {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => {
+                    let code = match name.strip_prefix("#x") {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => name.strip_prefix('#')?.parse().ok(),
+                    };
+                    char::from_u32(code?)
+                }
+            });
+        match entity {
+            Some(ch) => {
+                decoded.push(ch);
+                rest = &rest[(rest.find(';').unwrap_or(0) + 1)..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// How many columns some text takes up, not counting escape codes
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for ch in text.chars() {
+        match ch {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            _ => width += 1,
+        }
+    }
+    width
+}
+
+#[derive(Debug, Default)]
+struct Renderer {
+    styled: bool,
+    output: String,
+
+    /// The text of the block (paragraph, list item, ...) that's being built up
+    block: String,
+    in_article: bool,
+    in_pre: bool,
+    list_depth: usize,
+}
+
+impl Renderer {
+    fn style(&mut self, code: &str) {
+        if self.styled {
+            self.block.push_str(code);
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.in_pre {
+            self.block.push_str(text);
+            return;
+        }
+
+        // outside of code blocks any run of whitespace is a single space
+        let mut last_was_space = self.block.ends_with(' ') || self.block.is_empty();
+        for ch in text.chars() {
+            if ch.is_whitespace() {
+                if !last_was_space {
+                    self.block.push(' ');
+                }
+                last_was_space = true;
+            } else {
+                self.block.push(ch);
+                last_was_space = false;
+            }
+        }
+    }
+
+    /// Word-wraps the current block onto the output, starting the first line
+    /// with `first_prefix` and indenting the rest to match
+    fn flush(&mut self, first_prefix: &str) {
+        let block = std::mem::take(&mut self.block);
+        if block.trim().is_empty() {
+            return;
+        }
+
+        let indent = " ".repeat(first_prefix.len());
+        let mut line = first_prefix.to_string();
+        let mut line_width = first_prefix.len();
+        for word in block.split(' ').filter(|word| !word.is_empty()) {
+            let word_width = visible_width(word);
+            if line_width > indent.len() && line_width + 1 + word_width > WIDTH {
+                self.output.push_str(line.trim_end());
+                self.output.push('\n');
+                line = indent.clone();
+                line_width = indent.len();
+            }
+            if line_width > indent.len() {
+                line.push(' ');
+                line_width += 1;
+            }
+            line.push_str(word);
+            line_width += word_width;
+        }
+        self.output.push_str(line.trim_end());
+        self.output.push('\n');
+    }
+
+    fn blank_line(&mut self) {
+        if !self.output.is_empty() && !self.output.ends_with("\n\n") {
+            self.output.push('\n');
+        }
+    }
+
+    fn open(&mut self, tag: &str) {
+        match tag {
+            "article" => self.in_article = true,
+            _ if !self.in_article => {}
+            "h2" => {
+                self.flush("");
+                self.style(BOLD);
+            }
+            "p" => self.flush(""),
+            "pre" => {
+                self.flush("");
+                self.in_pre = true;
+            }
+            "ul" | "ol" => {
+                self.flush("");
+                self.list_depth += 1;
+            }
+            "li" => self.flush(""),
+            "em" => self.style(BOLD),
+            "code" if !self.in_pre => self.style(CYAN),
+            "br" => {
+                let prefix = self.list_prefix();
+                self.flush(&prefix);
+            }
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, tag: &str) {
+        if !self.in_article {
+            return;
+        }
+
+        match tag {
+            "article" => {
+                self.flush("");
+                self.blank_line();
+                self.in_article = false;
+            }
+            "h2" => {
+                self.style(NOT_BOLD);
+                self.flush("");
+                self.blank_line();
+            }
+            "p" => {
+                self.flush("");
+                self.blank_line();
+            }
+            "pre" => {
+                let block = std::mem::take(&mut self.block);
+                for line in block.trim_end_matches('\n').lines() {
+                    self.output.push_str("    ");
+                    self.output.push_str(line);
+                    self.output.push('\n');
+                }
+                self.blank_line();
+                self.in_pre = false;
+            }
+            "ul" | "ol" => {
+                self.flush("");
+                self.list_depth = self.list_depth.saturating_sub(1);
+                if self.list_depth == 0 {
+                    self.blank_line();
+                }
+            }
+            "li" => {
+                let prefix = self.list_prefix();
+                self.flush(&prefix);
+            }
+            "em" => self.style(NOT_BOLD),
+            "code" if !self.in_pre => self.style(DEFAULT_COLOR),
+            _ => {}
+        }
+    }
+
+    fn list_prefix(&self) -> String {
+        format!("{}- ", "  ".repeat(self.list_depth))
+    }
+}
+
+/// Turns the `<article>`s of a puzzle page into text for the terminal,
+/// keeping emphasis, code and lists. Without `styled` there are no escape
+/// codes in the output.
+pub fn render(html: &str, styled: bool) -> String {
+    let mut renderer = Renderer {
+        styled,
+        ..Default::default()
+    };
+
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        if renderer.in_article {
+            renderer.text(&decode_entities(&rest[..start]));
+        }
+        rest = &rest[start..];
+
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[(end + 1)..];
+
+        let (closing, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+        let name = tag
+            .split(|ch: char| ch.is_whitespace() || ch == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        if closing {
+            renderer.close(&name);
+        } else {
+            renderer.open(&name);
+        }
+    }
+
+    renderer.output.trim_end().to_string() + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART_1: &str = include_str!("../tests/fixtures/puzzle_part_1.html");
+    const BOTH_PARTS: &str = include_str!("../tests/fixtures/puzzle_both_parts.html");
+
+    #[test]
+    fn renders_part_1() {
+        assert!(!has_part_2(PART_1));
+        assert_eq!(
+            render(PART_1, false),
+            include_str!("../tests/fixtures/puzzle_part_1.txt")
+        );
+    }
+
+    #[test]
+    fn renders_both_parts() {
+        assert!(has_part_2(BOTH_PARTS));
+        let rendered = render(BOTH_PARTS, false);
+        assert!(rendered.starts_with(&render(PART_1, false)));
+        assert!(rendered.contains("--- Part Two ---"));
+        assert!(!rendered.contains("Your puzzle answer"));
+    }
+
+    #[test]
+    fn styles_emphasis_and_code() {
+        let rendered = render(
+            "<article><p>a <em>big</em> <code>x &lt; 3</code></p></article>",
+            true,
+        );
+        assert_eq!(rendered, "a \x1b[1mbig\x1b[22m \x1b[36mx < 3\x1b[39m\n");
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{
+    answer::Answer,
+    generate::InputGenerator,
+    params::{Param, Params},
+    visualize::Visualize,
+    y2021, y2022, Day, Part, Year,
+};
+
+pub type Solver = fn(&str, &Params) -> eyre::Result<Answer>;
+pub type Visualizer = for<'a> fn(&'a str, &Params) -> eyre::Result<Box<dyn Visualize + 'a>>;
+
+/// Looks up the solver for a puzzle, if there is one yet
+pub fn solver(year: Year, day: Day, part: Part) -> Option<Solver> {
+    let solver: Solver = match (year, day, part) {
+        (Year::Y2021, Day::D19, Part::Part1) => |input, _| Ok(y2021::d19::p1::solve(input)?.into()),
+        (Year::Y2021, Day::D19, Part::Part2) => |input, _| Ok(y2021::d19::p2::solve(input)?.into()),
+        (Year::Y2022, Day::D17, Part::Part1) => {
+            |input, params| Ok(y2022::d17::p1::solve(input, params)?.into())
+        }
+        (Year::Y2022, Day::D17, Part::Part2) => {
+            |input, params| Ok(y2022::d17::p2::solve(input, params)?.into())
+        }
+        (Year::Y2022, Day::D18, Part::Part1) => |input, _| Ok(y2022::d18::p1::solve(input)?.into()),
+        (Year::Y2022, Day::D18, Part::Part2) => |input, _| Ok(y2022::d18::p2::solve(input)?.into()),
+        (Year::Y2022, Day::D19, Part::Part1) => {
+            |input, params| Ok(y2022::d19::p1::solve(input, params)?.into())
+        }
+        (Year::Y2022, Day::D19, Part::Part2) => {
+            |input, params| Ok(y2022::d19::p2::solve(input, params)?.into())
+        }
+        (Year::Y2022, Day::D20, Part::Part1) => {
+            |input, params| Ok(y2022::d20::p1::solve(input, params)?.into())
+        }
+        (Year::Y2022, Day::D20, Part::Part2) => {
+            |input, params| Ok(y2022::d20::p2::solve(input, params)?.into())
+        }
+        (Year::Y2022, Day::D21, Part::Part1) => |input, _| Ok(y2022::d21::p1::solve(input)?.into()),
+        (Year::Y2022, Day::D21, Part::Part2) => |input, _| Ok(y2022::d21::p2::solve(input)?.into()),
+        (Year::Y2022, Day::D22, Part::Part1) => |input, _| Ok(y2022::d22::p1::solve(input)?.into()),
+        (Year::Y2022, Day::D22, Part::Part2) => |input, _| Ok(y2022::d22::p2::solve(input)?.into()),
+        (Year::Y2022, Day::D23, Part::Part1) => {
+            |input, params| Ok(y2022::d23::p1::solve(input, params)?.into())
+        }
+        (Year::Y2022, Day::D23, Part::Part2) => |input, _| Ok(y2022::d23::p2::solve(input)?.into()),
+        (Year::Y2022, Day::D24, Part::Part1) => |input, _| Ok(y2022::d24::p1::solve(input)?.into()),
+        (Year::Y2022, Day::D24, Part::Part2) => |input, _| Ok(y2022::d24::p2::solve(input)?.into()),
+        (Year::Y2022, Day::D25, Part::Part1) => |input, _| Ok(y2022::d25::p1::solve(input).into()),
+        _ => return None,
+    };
+    Some(solver)
+}
+
+/// Looks up the parameters a solver can be tuned with
+pub fn params(year: Year, day: Day, part: Part) -> &'static [Param] {
+    match (year, day, part) {
+        (Year::Y2022, Day::D17, Part::Part1) => y2022::d17::p1::PARAMS,
+        (Year::Y2022, Day::D17, Part::Part2) => y2022::d17::p2::PARAMS,
+        (Year::Y2022, Day::D19, Part::Part1) => y2022::d19::p1::PARAMS,
+        (Year::Y2022, Day::D19, Part::Part2) => y2022::d19::p2::PARAMS,
+        (Year::Y2022, Day::D20, Part::Part1) => y2022::d20::p1::PARAMS,
+        (Year::Y2022, Day::D20, Part::Part2) => y2022::d20::p2::PARAMS,
+        (Year::Y2022, Day::D23, Part::Part1) => y2022::d23::p1::PARAMS,
+        _ => &[],
+    }
+}
+
+/// Looks up the input generator for a puzzle, if it has one
+pub fn generator(year: Year, day: Day) -> Option<&'static dyn InputGenerator> {
+    match (year, day) {
+        (Year::Y2022, Day::D17) => Some(&y2022::d17::Generator),
+        (Year::Y2022, Day::D18) => Some(&y2022::d18::Generator),
+        (Year::Y2022, Day::D19) => Some(&y2022::d19::Generator),
+        (Year::Y2022, Day::D20) => Some(&y2022::d20::Generator),
+        (Year::Y2022, Day::D23) => Some(&y2022::d23::Generator),
+        (Year::Y2022, Day::D25) => Some(&y2022::d25::Generator),
+        _ => None,
+    }
+}
+
+/// Looks up the visualization for a puzzle, if it has one
+pub fn visualizer(year: Year, day: Day, part: Part) -> Option<Visualizer> {
+    let visualizer: Visualizer = match (year, day, part) {
+        (Year::Y2022, Day::D17, Part::Part1) => {
+            |input, params| Ok(Box::new(y2022::d17::p1::visualize(input, params)?))
+        }
+        (Year::Y2022, Day::D17, Part::Part2) => {
+            |input, params| Ok(Box::new(y2022::d17::p2::visualize(input, params)?))
+        }
+        (Year::Y2022, Day::D22, Part::Part1) => {
+            |input, _| Ok(Box::new(y2022::d22::p1::visualize(input)?))
+        }
+        (Year::Y2022, Day::D22, Part::Part2) => {
+            |input, _| Ok(Box::new(y2022::d22::p2::visualize(input)?))
+        }
+        (Year::Y2022, Day::D23, Part::Part1) => {
+            |input, params| Ok(Box::new(y2022::d23::p1::visualize(input, params)?))
+        }
+        (Year::Y2022, Day::D23, Part::Part2) => {
+            |input, _| Ok(Box::new(y2022::d23::p2::visualize(input)?))
+        }
+        (Year::Y2022, Day::D24, _) => |input, _| Ok(Box::new(y2022::d24::visualize(input)?)),
+        _ => return None,
+    };
+    Some(visualizer)
+}
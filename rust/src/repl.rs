@@ -0,0 +1,328 @@
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+};
+
+use clap::ValueEnum;
+
+use crate::{
+    config, fetch_input, params::Params, registry, runner, watch::trim_input, Day, Part, Year,
+};
+
+/// Where the example fixtures (see `build.rs`) are when running from the
+/// crate's directory
+const EXAMPLES_DIR: &str = "tests/examples";
+
+/// Finds the example fixtures, either in the crate's directory or in an
+/// `examples` directory in the cache directory
+fn examples_dir() -> eyre::Result<PathBuf> {
+    let candidates = [
+        PathBuf::from(EXAMPLES_DIR),
+        config::get().cache_dir.join("examples"),
+    ];
+    candidates
+        .iter()
+        .find(|dir| dir.is_dir())
+        .cloned()
+        .ok_or_else(|| {
+            eyre::eyre!(
+                "can't find the examples, which should be in {} or {}",
+                candidates[0].display(),
+                candidates[1].display()
+            )
+        })
+}
+
+/// How many times `time` runs a solver when not told otherwise
+const DEFAULT_TIMES: u64 = 10;
+
+const HELP: &str = "\
+load [year] <day>       load a puzzle and its input
+example <n>             use the puzzle's nth example instead of its input
+input                   go back to the puzzle's real input
+run <p1|p2|both>        solve a part (or both)
+time <p1|p2> [times]    solve a part several times and show the timings
+show input [lines]      print the input, or some of its lines (e.g. 10..20)
+help                    show this message
+quit                    leave the repl";
+
+/// What `show input` should print, in 1-based line numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LineRange {
+    start: usize,
+    end: Option<usize>,
+}
+
+impl LineRange {
+    /// Parses `10`, `10..20`, `10..=20`, `10..` or `..20`
+    fn parse(range: &str) -> Option<Self> {
+        let number = |number: &str| number.parse::<usize>().ok();
+        let Some((start, end)) = range.split_once("..") else {
+            let line = number(range)?;
+            return Some(Self {
+                start: line,
+                end: Some(line + 1),
+            });
+        };
+
+        let start = if start.is_empty() { 1 } else { number(start)? };
+        let end = match end.strip_prefix('=') {
+            Some(end) => Some(number(end)? + 1),
+            None if end.is_empty() => None,
+            None => Some(number(end)?),
+        };
+        Some(Self { start, end })
+    }
+}
+
+/// Whether the repl should keep going after a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    Quit,
+}
+
+#[derive(Debug, Default)]
+struct Session {
+    puzzle: Option<(Year, Day)>,
+    /// The input that solvers get run on, and where it came from
+    input: Option<(String, String)>,
+}
+
+impl Session {
+    fn puzzle(&self) -> eyre::Result<(Year, Day)> {
+        self.puzzle
+            .ok_or_else(|| eyre::eyre!("no puzzle is loaded, try `load 2022 24`"))
+    }
+
+    fn input(&self) -> eyre::Result<&str> {
+        self.input
+            .as_ref()
+            .map(|(input, _)| input.as_str())
+            .ok_or_else(|| eyre::eyre!("no input is loaded"))
+    }
+
+    fn load(&mut self, year: Year, day: &str) -> eyre::Result<String> {
+        let day = Day::from_str(day, false)
+            .map_err(|_| eyre::eyre!("`{day}` isn't a day, expected 1 to 25"))?;
+        self.puzzle = Some((year, day));
+        self.input = None;
+        self.load_input()
+    }
+
+    fn load_input(&mut self) -> eyre::Result<String> {
+        let (year, day) = self.puzzle()?;
+        self.input = Some((fetch_input(day, year)?, "the input".to_string()));
+        Ok(format!("Loaded the input for {year} day {day}"))
+    }
+
+    fn load_example(&mut self, n: usize) -> eyre::Result<String> {
+        let (year, day) = self.puzzle()?;
+        let dir = examples_dir()?.join(format!("y{year}/d{day}"));
+
+        // `input.txt` sorts first, followed by `input-{case}.txt`s
+        let mut examples: Vec<String> = fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                    .filter(|name| name.starts_with("input") && name.ends_with(".txt"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        examples.sort_by_key(|name| name.trim_end_matches(".txt").to_string());
+
+        let name = n
+            .checked_sub(1)
+            .and_then(|i| examples.get(i))
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "{year} day {day} has {} example(s) in {}",
+                    examples.len(),
+                    dir.display()
+                )
+            })?;
+        let input = fs::read_to_string(dir.join(name))?;
+        self.input = Some((trim_input(&input).to_string(), format!("example {n}")));
+        Ok(format!("Loaded example {n} ({name}) for {year} day {day}"))
+    }
+
+    fn parts(part: &str) -> eyre::Result<Vec<Part>> {
+        if part == "both" {
+            return Ok(Part::value_variants().to_vec());
+        }
+        Part::from_str(part, true)
+            .map(|part| vec![part])
+            .map_err(|_| eyre::eyre!("`{part}` isn't a part, expected p1, p2 or both"))
+    }
+
+    fn run(&self, part: &str) -> eyre::Result<String> {
+        let (year, day) = self.puzzle()?;
+        let input = self.input()?;
+
+        let mut lines = Vec::new();
+        for part in Self::parts(part)? {
+            let Some(solver) = registry::solver(year, day, part) else {
+                lines.push(format!("There is not yet a solution for {part}"));
+                continue;
+            };
+            let (answer, elapsed) = runner::time(solver, input, &Params::default())?;
+            lines.push(format!(
+                "The solution for {part} is {answer} (took {elapsed:?})"
+            ));
+            lines.extend(answer.art().map(str::to_string));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    fn time(&self, part: &str, times: Option<&str>) -> eyre::Result<String> {
+        let (year, day) = self.puzzle()?;
+        let input = self.input()?;
+        let times = match times {
+            Some(times) => times.parse()?,
+            None => DEFAULT_TIMES,
+        };
+
+        let mut lines = Vec::new();
+        for part in Self::parts(part)? {
+            let solver = registry::solver(year, day, part)
+                .ok_or_else(|| eyre::eyre!("There is not yet a solution for {part}"))?;
+            let stats = runner::repeat(
+                solver,
+                input,
+                &Params::default(),
+                runner::RepeatLimit::Times(times),
+            )?;
+            lines.push(format!("Solved {part}: {stats}"));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    fn show(&self, range: Option<&str>) -> eyre::Result<String> {
+        let input = self.input()?;
+        let range = match range {
+            Some(range) => LineRange::parse(range)
+                .ok_or_else(|| eyre::eyre!("`{range}` isn't a range of lines, try 10..20"))?,
+            None => LineRange {
+                start: 1,
+                end: None,
+            },
+        };
+
+        let width = input.lines().count().to_string().len();
+        let lines: Vec<String> = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|&(number, _)| {
+                number >= range.start && range.end.is_none_or(|end| number < end)
+            })
+            .map(|(number, line)| format!("{number:>width$} | {line}"))
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    /// Runs a line typed at the prompt, returning what to print
+    fn execute(&mut self, line: &str) -> eyre::Result<(Flow, String)> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let output = match words[..] {
+            [] => String::new(),
+            ["quit" | "exit"] => return Ok((Flow::Quit, String::new())),
+            ["help"] => HELP.to_string(),
+            ["load", day] => {
+                let year = config::get()
+                    .year
+                    .ok_or_else(|| eyre::eyre!("there's no default year, try `load 2022 {day}`"))?;
+                self.load(year, day)?
+            }
+            ["load", year, day] => {
+                let year = Year::from_str(year, false)
+                    .map_err(|_| eyre::eyre!("there are no puzzles for {year}"))?;
+                self.load(year, day)?
+            }
+            ["input"] => self.load_input()?,
+            ["example", n] => self.load_example(n.parse()?)?,
+            ["run", part] => self.run(part)?,
+            ["time", part] => self.time(part, None)?,
+            ["time", part, times] => self.time(part, Some(times))?,
+            ["show", "input"] => self.show(None)?,
+            ["show", "input", range] => self.show(Some(range))?,
+            _ => eyre::bail!("unknown command `{}`, try `help`", line.trim()),
+        };
+        Ok((Flow::Continue, output))
+    }
+
+    fn prompt(&self) -> String {
+        match (self.puzzle, &self.input) {
+            (Some((year, day)), Some((_, source))) => format!("{year} day {day} ({source})> "),
+            (Some((year, day)), None) => format!("{year} day {day}> "),
+            (None, _) => "aoc> ".to_string(),
+        }
+    }
+}
+
+/// Reads commands from stdin until it's closed or the user quits
+pub fn repl() -> eyre::Result<()> {
+    println!("Type `help` to see the commands");
+
+    let mut session = Session::default();
+    let mut stdin = io::stdin().lock();
+    loop {
+        print!("{}", session.prompt());
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if stdin.read_line(&mut line)? == 0 {
+            println!();
+            return Ok(());
+        }
+
+        // a mistake shouldn't throw away what's been loaded
+        match session.execute(&line) {
+            Ok((Flow::Quit, _)) => return Ok(()),
+            Ok((Flow::Continue, output)) if output.is_empty() => {}
+            Ok((Flow::Continue, output)) => println!("{output}"),
+            Err(err) => println!("error: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_line_ranges() {
+        let range = |start, end| Some(LineRange { start, end });
+        assert_eq!(LineRange::parse("10..20"), range(10, Some(20)));
+        assert_eq!(LineRange::parse("10..=20"), range(10, Some(21)));
+        assert_eq!(LineRange::parse("10.."), range(10, None));
+        assert_eq!(LineRange::parse("..3"), range(1, Some(3)));
+        assert_eq!(LineRange::parse("7"), range(7, Some(8)));
+        assert_eq!(LineRange::parse("a..b"), None);
+    }
+
+    #[test]
+    fn runs_examples() {
+        let mut session = Session {
+            puzzle: Some((Year::Y2022, Day::D18)),
+            input: None,
+        };
+        assert!(session.execute("run p1").is_err());
+
+        session.execute("example 2").unwrap();
+        assert_eq!(session.prompt(), "2022 day 18 (example 2)> ");
+        let (_, output) = session.execute("run both").unwrap();
+        assert!(output.starts_with("The solution for part 1 is 10 (took"));
+        assert!(output.contains("The solution for part 2 is 10 (took"));
+
+        let (_, output) = session.execute("show input 2..").unwrap();
+        assert_eq!(output, "2 | 2,1,1");
+
+        let (_, output) = session.execute("time p1 3").unwrap();
+        assert!(output.starts_with("Solved part 1: 3 iterations in"));
+
+        assert!(session.execute("example 3").is_err());
+        assert_eq!(session.execute("quit").unwrap().0, Flow::Quit);
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    fmt::Display,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{answer::Answer, params::Params, registry::Solver};
+
+/// Runs a solver once, returning its answer and how long it took
+pub fn time(solver: Solver, input: &str, params: &Params) -> eyre::Result<(Answer, Duration)> {
+    let start = Instant::now();
+    let answer = solver(input, params)?;
+    Ok((answer, start.elapsed()))
+}
+
+/// How long to keep calling a solver for in [`repeat`]
+#[derive(Debug, Clone, Copy)]
+pub enum RepeatLimit {
+    Times(u64),
+    For(Duration),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RepeatStats {
+    pub iterations: u64,
+    pub total: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl Display for RepeatStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mean = self.total / self.iterations.try_into().unwrap_or(u32::MAX);
+        write!(
+            f,
+            "{} iterations in {:?} (mean {mean:?}, min {:?}, max {:?})",
+            self.iterations, self.total, self.min, self.max
+        )
+    }
+}
+
+/// Calls a solver on the same input over and over, so that there's enough
+/// going on for a sampling profiler to pick up
+pub fn repeat(
+    solver: Solver,
+    input: &str,
+    params: &Params,
+    limit: RepeatLimit,
+) -> eyre::Result<RepeatStats> {
+    let mut stats = RepeatStats {
+        iterations: 0,
+        total: Duration::ZERO,
+        min: Duration::MAX,
+        max: Duration::ZERO,
+    };
+
+    loop {
+        let done = match limit {
+            RepeatLimit::Times(times) => stats.iterations >= times,
+            RepeatLimit::For(duration) => stats.total >= duration,
+        };
+        if done {
+            break;
+        }
+
+        let start = Instant::now();
+        // the black boxes stop the optimizer from hoisting the solve out of the
+        // loop or throwing the answer away
+        black_box(solver(black_box(input), params)?);
+        let elapsed = start.elapsed();
+
+        stats.iterations += 1;
+        stats.total += elapsed;
+        stats.min = stats.min.min(elapsed);
+        stats.max = stats.max.max(elapsed);
+    }
+
+    eyre::ensure!(stats.iterations > 0, "the solver was never run");
+    Ok(stats)
+}
+
+/// Parses durations like `500ms`, `10s` or `2m`; a bare number is in seconds
+pub fn parse_duration(duration: &str) -> Result<Duration, String> {
+    let duration = duration.trim();
+    let split = duration
+        .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+        .unwrap_or(duration.len());
+    let (amount, unit) = duration.split_at(split);
+
+    let amount = amount
+        .parse::<f64>()
+        .map_err(|err| format!("can't parse `{amount}` as a number: {err}"))?;
+    let seconds = match unit {
+        "ms" => amount / 1000.0,
+        "" | "s" => amount,
+        "m" => amount * 60.0,
+        _ => return Err(format!("unknown unit `{unit}` (expected ms, s or m)")),
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("10"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("1.5m"), Ok(Duration::from_secs(90)));
+        assert!(parse_duration("10h").is_err());
+        assert!(parse_duration("s").is_err());
+    }
+}
@@ -0,0 +1,306 @@
+use std::{
+    io::Read,
+    net::{IpAddr, SocketAddr},
+    sync::mpsc,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::{
+    answer::Solution,
+    params::{self, Params},
+    registry, runner, Day, Part, Year,
+};
+
+/// The most input a solve request can send, well over any real puzzle's
+const MAX_INPUT_BYTES: u64 = 1 << 20;
+
+#[derive(Debug, Serialize)]
+struct Puzzle {
+    year: u16,
+    day: u8,
+    part: u8,
+}
+
+#[derive(Debug, Serialize)]
+struct Failure {
+    error: String,
+}
+
+/// A status code and the JSON to send back with it
+type Reply = (u16, String);
+
+fn reply(status: u16, body: &impl Serialize) -> Reply {
+    let body = serde_json::to_string(body).expect("our responses always serialize");
+    (status, body)
+}
+
+fn failure(status: u16, error: impl Into<String>) -> Reply {
+    reply(
+        status,
+        &Failure {
+            error: error.into(),
+        },
+    )
+}
+
+fn puzzles() -> Vec<Puzzle> {
+    let mut puzzles = Vec::new();
+    for &year in Year::value_variants() {
+        for &day in Day::value_variants() {
+            for &part in Part::value_variants() {
+                if registry::solver(year, day, part).is_some() {
+                    puzzles.push(Puzzle {
+                        year: year.number(),
+                        day: day.number(),
+                        part: part.number(),
+                    });
+                }
+            }
+        }
+    }
+    puzzles
+}
+
+/// Parses the `{year}/{day}/{part}` of a solve URL; parts can be given as
+/// either `1` or `p1`
+fn parse_puzzle(path: &str) -> Option<(Year, Day, Part)> {
+    let mut segments = path.split('/');
+    let year = Year::from_str(segments.next()?, false).ok()?;
+    let day = Day::from_str(segments.next()?, false).ok()?;
+    let part = segments.next()?;
+    let part = Part::from_str(part, true)
+        .or_else(|_| Part::from_str(&format!("p{part}"), true))
+        .ok()?;
+    segments.next().is_none().then_some((year, day, part))
+}
+
+/// Parses the parameters given in a query string like `rounds=20&key=1`,
+/// where the keys and values can be percent-encoded
+fn parse_params(query: &str) -> Result<Params, String> {
+    let params = form_urlencoded::parse(query.as_bytes())
+        .map(|(name, value)| Ok((name.trim().to_string(), params::parse_value(&value)?)))
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(Params::new(params))
+}
+
+/// Runs solvers on other threads, giving up on them after `timeout`. There's
+/// no way to stop a thread from the outside, so a solver that times out keeps
+/// running in the background until it finishes, and no more are started
+/// until it has so that they can't pile up.
+#[derive(Debug)]
+struct Solves {
+    timeout: Duration,
+    /// A solver that timed out but is still running
+    abandoned: Option<JoinHandle<()>>,
+}
+
+impl Solves {
+    fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            abandoned: None,
+        }
+    }
+
+    /// Whether a solver that timed out is still running
+    fn busy(&mut self) -> bool {
+        if self
+            .abandoned
+            .as_ref()
+            .is_some_and(|abandoned| abandoned.is_finished())
+        {
+            self.abandoned = None;
+        }
+        self.abandoned.is_some()
+    }
+}
+
+fn solve(
+    (year, day, part): (Year, Day, Part),
+    query: &str,
+    input: String,
+    solves: &mut Solves,
+) -> Reply {
+    let Some(solver) = registry::solver(year, day, part) else {
+        return failure(
+            404,
+            format!("there is no solution for {year} day {day} {part}"),
+        );
+    };
+
+    let params = match parse_params(query) {
+        Ok(params) => params,
+        Err(err) => return failure(400, err),
+    };
+    if let Err(err) = params.check(registry::params(year, day, part)) {
+        return failure(400, err.to_string());
+    }
+
+    if solves.busy() {
+        return failure(
+            503,
+            "a solve that timed out is still running, try again later",
+        );
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let worker = thread::spawn(move || {
+        let result = runner::time(solver, &input, &params).map_err(|err| err.to_string());
+        let _ = sender.send(result);
+    });
+
+    let timeout = solves.timeout;
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok((answer, elapsed))) => reply(200, &Solution::new(year, day, part, answer, elapsed)),
+        Ok(Err(err)) => failure(422, err),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            solves.abandoned = Some(worker);
+            failure(504, format!("gave up after {timeout:?}"))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => failure(500, "the solver panicked"),
+    }
+}
+
+/// Reads a request's body, as long as it isn't too big
+fn read_input(reader: impl Read) -> Result<String, Reply> {
+    let mut input = String::new();
+    reader
+        .take(MAX_INPUT_BYTES + 1)
+        .read_to_string(&mut input)
+        .map_err(|err| failure(400, format!("the input has to be UTF-8 text: {err}")))?;
+    if input.len() as u64 > MAX_INPUT_BYTES {
+        return Err(failure(
+            413,
+            format!("the input can't be more than {MAX_INPUT_BYTES} bytes"),
+        ));
+    }
+    Ok(input)
+}
+
+fn handle(method: &Method, url: &str, body: String, solves: &mut Solves) -> Reply {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    match (method, path) {
+        (Method::Get, "/puzzles") => reply(200, &puzzles()),
+        (Method::Post, path) if path.starts_with("/solve/") => {
+            match parse_puzzle(&path["/solve/".len()..]) {
+                Some(puzzle) => solve(puzzle, query, body, solves),
+                None => failure(404, "expected /solve/{year}/{day}/{part}"),
+            }
+        }
+        (_, "/puzzles") => failure(405, "only GET is allowed here"),
+        (_, path) if path.starts_with("/solve/") => failure(405, "only POST is allowed here"),
+        _ => failure(404, format!("there's nothing at {path}")),
+    }
+}
+
+/// Serves solutions over HTTP until killed. Requests are handled one at a
+/// time.
+pub fn serve(host: IpAddr, port: u16, timeout: Duration) -> eyre::Result<()> {
+    let address = SocketAddr::new(host, port);
+    let server =
+        Server::http(address).map_err(|err| eyre::eyre!("couldn't bind {address}: {err}"))?;
+    println!("Listening on http://{address}");
+
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("the header is valid");
+    let mut solves = Solves::new(timeout);
+    for mut request in server.incoming_requests() {
+        let (status, json) = match read_input(request.as_reader()) {
+            Ok(body) => handle(request.method(), request.url(), body, &mut solves),
+            Err(reply) => reply,
+        };
+
+        let response = Response::from_string(json)
+            .with_status_code(status)
+            .with_header(content_type.clone());
+        if let Err(err) = request.respond(response) {
+            eprintln!("warning: couldn't send a response: {err}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn lists_puzzles() {
+        let solves = &mut Solves::new(TIMEOUT);
+        let (status, json) = handle(&Method::Get, "/puzzles", String::new(), solves);
+        assert_eq!(status, 200);
+        assert!(json.contains(r#"{"year":2022,"day":17,"part":1}"#));
+    }
+
+    #[test]
+    fn solves_puzzles() {
+        let solves = &mut Solves::new(TIMEOUT);
+        let input = "1=-0-2\n12111\n2=0=".to_string();
+        let (status, json) = handle(&Method::Post, "/solve/2022/25/1", input.clone(), solves);
+        assert_eq!(status, 200);
+        assert!(json.starts_with(
+            r#"{"year":2022,"day":25,"part":1,"answer":{"type":"text","value":"10=-01"}"#
+        ));
+
+        let (status, _) = handle(&Method::Post, "/solve/2022/25/p1", input, solves);
+        assert_eq!(status, 200);
+
+        let elves = "##\n#.\n..\n##".to_string();
+        let (_, json) = handle(&Method::Post, "/solve/2022/23/1?rounds=0", elves, solves);
+        assert!(
+            json.contains(r#""answer":{"type":"number","value":3}"#),
+            "{json}"
+        );
+    }
+
+    #[test]
+    fn decodes_query_strings() {
+        let params = parse_params("%72ounds=%2D1&key=1_000&").unwrap();
+        let expected = [("rounds".to_string(), -1), ("key".to_string(), 1000)];
+        assert_eq!(params, Params::new(expected));
+        // `+` is a space, which isn't part of a number
+        assert!(parse_params("rounds=1+2").is_err());
+    }
+
+    #[test]
+    fn rejects_bad_requests() {
+        let solves = &mut Solves::new(TIMEOUT);
+        let mut post =
+            |url: &str, body: &str| handle(&Method::Post, url, body.to_string(), solves).0;
+        assert_eq!(post("/solve/2022/1/1", ""), 404);
+        assert_eq!(post("/solve/2022/25", ""), 404);
+        assert_eq!(post("/solve/2022/18/1", "not a cube"), 422);
+        assert_eq!(post("/puzzles", ""), 405);
+        assert_eq!(post("/solve/2022/25/1?rounds=2", "1"), 400);
+        assert_eq!(post("/solve/2022/23/1?rounds", "#"), 400);
+        assert_eq!(post("/solve/2022/23/1?rounds=%2", "#"), 400);
+
+        let too_big = "1".repeat(MAX_INPUT_BYTES as usize + 1);
+        assert_eq!(read_input(too_big.as_bytes()).unwrap_err().0, 413);
+        assert_eq!(read_input(&too_big.as_bytes()[1..]).unwrap(), too_big[1..]);
+    }
+
+    #[test]
+    fn waits_for_timed_out_solves() {
+        let solves = &mut Solves::new(Duration::ZERO);
+        // slow enough to time out straight away, and quick enough to wait for
+        let blueprints = include_str!("../tests/examples/y2022/d19/input.txt");
+        let post = |solves: &mut Solves| {
+            let url = "/solve/2022/19/1";
+            handle(&Method::Post, url, blueprints.to_string(), solves).0
+        };
+        assert_eq!(post(solves), 504);
+        assert_eq!(post(solves), 503);
+
+        solves.abandoned.take().unwrap().join().unwrap();
+        solves.timeout = TIMEOUT;
+        assert_eq!(post(solves), 200);
+    }
+}
@@ -0,0 +1,328 @@
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+/// A row of bits of any width, packed 64 to a word so that whole rows can be
+/// combined at once. Bit `x` is column `x`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitRow {
+    width: usize,
+    words: Vec<u64>,
+}
+
+impl BitRow {
+    /// A row with every bit cleared
+    pub fn new(width: usize) -> Self {
+        Self {
+            width,
+            words: vec![0; width.div_ceil(64)],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Panics if `x` is outside the row
+    pub fn get(&self, x: usize) -> bool {
+        assert!(
+            x < self.width,
+            "column {x} is outside a row {} wide",
+            self.width
+        );
+        self.words[x / 64] & (1 << (x % 64)) != 0
+    }
+
+    /// Panics if `x` is outside the row
+    pub fn set(&mut self, x: usize, value: bool) {
+        assert!(
+            x < self.width,
+            "column {x} is outside a row {} wide",
+            self.width
+        );
+        if value {
+            self.words[x / 64] |= 1 << (x % 64);
+        } else {
+            self.words[x / 64] &= !(1 << (x % 64));
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// The columns of the set bits, from left to right
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    i * 64 + bit
+                })
+            })
+        })
+    }
+
+    /// Moves every bit `by` columns to the right (or to the left if `by` is
+    /// negative), dropping the ones that fall off the end
+    pub fn shifted(&self, by: isize) -> Self {
+        let mut shifted = Self::new(self.width);
+        let (words, bits) = (by.unsigned_abs() / 64, by.unsigned_abs() % 64);
+        let word = |i: Option<usize>| i.and_then(|i| self.words.get(i)).copied().unwrap_or(0);
+
+        for (i, shifted_word) in shifted.words.iter_mut().enumerate() {
+            *shifted_word = if by >= 0 {
+                let low = word(i.checked_sub(words));
+                let carried = word(i.checked_sub(words + 1));
+                low << bits | if bits == 0 { 0 } else { carried >> (64 - bits) }
+            } else {
+                let high = word(Some(i + words));
+                let carried = word(Some(i + words + 1));
+                high >> bits | if bits == 0 { 0 } else { carried << (64 - bits) }
+            };
+        }
+        shifted.clear_padding();
+        shifted
+    }
+
+    /// Clears the bits past the end of the row in the last word, which
+    /// shifting and negating can set
+    fn clear_padding(&mut self) {
+        if let (Some(last), extra @ 1..) = (self.words.last_mut(), self.width % 64) {
+            *last &= (1 << extra) - 1;
+        }
+    }
+}
+
+/// A grid of bits, one [`BitRow`] per row, for checking and updating which
+/// tiles are occupied a whole row at a time
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    rows: Vec<BitRow>,
+}
+
+impl BitGrid {
+    /// A grid with every bit cleared
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            rows: vec![BitRow::new(width); height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Panics if `(x, y)` is outside the grid
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.rows[y].get(x)
+    }
+
+    /// Panics if `(x, y)` is outside the grid
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        self.rows[y].set(x, value);
+    }
+
+    pub fn row(&self, y: usize) -> &BitRow {
+        &self.rows[y]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut BitRow {
+        &mut self.rows[y]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &BitRow> {
+        self.rows.iter()
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.rows.iter().map(BitRow::count_ones).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().all(BitRow::is_empty)
+    }
+
+    /// The positions of the set bits, row by row
+    pub fn ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.ones().map(move |x| (x, y)))
+    }
+
+    /// A copy with `by` empty rows and columns added on every side
+    pub fn padded(&self, by: usize) -> Self {
+        let mut padded = Self::new(self.width + 2 * by, self.height() + 2 * by);
+        for (x, y) in self.ones() {
+            padded.set(x + by, y + by, true);
+        }
+        padded
+    }
+
+    /// Moves every bit `dx` columns right and `dy` rows down (negative values
+    /// going left and up), dropping the ones that fall off the edges
+    pub fn shifted(&self, dx: isize, dy: isize) -> Self {
+        let empty = BitRow::new(self.width);
+        let rows = (0..self.height())
+            .map(
+                |y| match y.checked_add_signed(-dy).and_then(|y| self.rows.get(y)) {
+                    Some(row) => row.shifted(dx),
+                    None => empty.clone(),
+                },
+            )
+            .collect();
+        Self {
+            width: self.width,
+            rows,
+        }
+    }
+
+    /// The tiles that are set or are next to one that is, not counting
+    /// diagonally: where something could be after taking a step or staying
+    /// put
+    pub fn spread(&self) -> Self {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .fold(self.clone(), |spread, (dx, dy)| {
+                &spread | &self.shifted(dx, dy)
+            })
+    }
+
+    /// The tiles with at least one set tile among the eight around them
+    pub fn surrounded(&self) -> Self {
+        let across = &self.shifted(-1, 0) | &self.shifted(1, 0);
+        let rows = &(&across | self).shifted(0, -1) | &(&across | self).shifted(0, 1);
+        &rows | &across
+    }
+}
+
+/// Combines rows and grids bit by bit, which only makes sense if they're the
+/// same size
+macro_rules! impl_bit_op {
+    ($op:ident, $method:ident) => {
+        impl $op for &BitRow {
+            type Output = BitRow;
+
+            fn $method(self, other: Self) -> BitRow {
+                assert_eq!(self.width, other.width, "the rows are different widths");
+                BitRow {
+                    width: self.width,
+                    words: self
+                        .words
+                        .iter()
+                        .zip(&other.words)
+                        .map(|(a, b)| a.$method(b))
+                        .collect(),
+                }
+            }
+        }
+
+        impl $op for &BitGrid {
+            type Output = BitGrid;
+
+            fn $method(self, other: Self) -> BitGrid {
+                assert_eq!(
+                    self.height(),
+                    other.height(),
+                    "the grids are different heights"
+                );
+                BitGrid {
+                    width: self.width,
+                    rows: self
+                        .rows
+                        .iter()
+                        .zip(&other.rows)
+                        .map(|(a, b)| a.$method(b))
+                        .collect(),
+                }
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand);
+impl_bit_op!(BitOr, bitor);
+impl_bit_op!(BitXor, bitxor);
+
+impl Not for &BitRow {
+    type Output = BitRow;
+
+    fn not(self) -> BitRow {
+        let mut row = BitRow {
+            width: self.width,
+            words: self.words.iter().map(|word| !word).collect(),
+        };
+        row.clear_padding();
+        row
+    }
+}
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        BitGrid {
+            width: self.width,
+            rows: self.rows.iter().map(|row| !row).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(width: usize, ones: &[usize]) -> BitRow {
+        let mut row = BitRow::new(width);
+        for &x in ones {
+            row.set(x, true);
+        }
+        row
+    }
+
+    #[test]
+    fn shifts_across_words() {
+        let original = row(150, &[0, 63, 64, 149]);
+        assert_eq!(original.shifted(1), row(150, &[1, 64, 65]));
+        assert_eq!(original.shifted(-1), row(150, &[62, 63, 148]));
+        assert_eq!(original.shifted(70), row(150, &[70, 133, 134]));
+        assert_eq!(original.shifted(-64), row(150, &[0, 85]));
+        assert_eq!(original.shifted(150), BitRow::new(150));
+        assert_eq!((!&original).count_ones(), 146);
+        assert_eq!(original.ones().collect::<Vec<_>>(), [0, 63, 64, 149]);
+    }
+
+    #[test]
+    fn finds_neighbors() {
+        let mut grid = BitGrid::new(5, 5);
+        grid.set(2, 2, true);
+        grid.set(0, 0, true);
+
+        let spread = grid.spread();
+        assert_eq!(spread.count_ones(), 8);
+        assert!(spread.get(2, 1) && spread.get(1, 0) && !spread.get(1, 1));
+
+        let surrounded = grid.surrounded();
+        assert_eq!(surrounded.count_ones(), 10);
+        assert!(!surrounded.get(2, 2) && surrounded.get(1, 1) && surrounded.get(3, 3));
+        assert!((&surrounded & &grid).is_empty());
+
+        let padded = grid.padded(2);
+        assert_eq!((padded.width(), padded.height()), (9, 9));
+        assert_eq!(padded.ones().collect::<Vec<_>>(), [(2, 2), (4, 4)]);
+    }
+}
@@ -0,0 +1,131 @@
+use std::{collections::HashSet, fmt::Display, hash::Hash};
+
+/// An optimisation puzzle where every state can be turned into a handful of
+/// others, and some reachable state has the best value
+pub trait Problem {
+    /// Everything that decides what can happen next and what it's worth, so
+    /// that getting to the same state twice never needs looking at again
+    type State: Clone + Eq + Hash;
+    type Value: Copy + Ord;
+
+    /// The states that can be reached from `state` in one move, the most
+    /// promising first so that good values are found (and used to prune)
+    /// early
+    fn children(&self, state: &Self::State) -> Vec<Self::State>;
+
+    /// What `state` is worth if the search stops there
+    fn value(&self, state: &Self::State) -> Self::Value;
+
+    /// The most that `state` or anything reachable from it could be worth.
+    /// It must never be less than the real best, or the search might prune
+    /// its way past it.
+    fn upper_bound(&self, state: &Self::State) -> Self::Value;
+}
+
+/// How much work a search did, for profiling
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// How many states had their children looked at
+    pub expanded: usize,
+    /// How many states were skipped for having been seen before
+    pub repeated: usize,
+    /// How many states were skipped for not being able to beat the best
+    pub pruned: usize,
+}
+
+impl Display for Metrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expanded {} states, skipped {} repeats and pruned {}",
+            self.expanded, self.repeated, self.pruned
+        )
+    }
+}
+
+/// Finds the best value of any state reachable from `start`, going depth
+/// first and skipping every state whose upper bound can't beat the best value
+/// found so far
+pub fn maximize<P: Problem>(problem: &P, start: P::State) -> (P::Value, Metrics) {
+    let mut metrics = Metrics::default();
+    let mut best = problem.value(&start);
+    let mut seen = HashSet::new();
+    let mut stack = vec![start];
+
+    while let Some(state) = stack.pop() {
+        if problem.upper_bound(&state) <= best {
+            metrics.pruned += 1;
+            continue;
+        }
+        if !seen.insert(state.clone()) {
+            metrics.repeated += 1;
+            continue;
+        }
+
+        metrics.expanded += 1;
+        best = best.max(problem.value(&state));
+        // the stack is last in first out, so the first child gets pushed last
+        let mut children = problem.children(&state);
+        children.reverse();
+        stack.extend(children);
+    }
+
+    (best, metrics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Picking items to fit in a bag without going over its capacity
+    struct Knapsack {
+        /// The weight and value of each item
+        items: Vec<(u32, u32)>,
+        capacity: u32,
+    }
+
+    /// The next item to decide on, and the weight and value of what's in
+    /// the bag so far
+    type Bag = (usize, u32, u32);
+
+    impl Problem for Knapsack {
+        type State = Bag;
+        type Value = u32;
+
+        fn children(&self, &(next, weight, value): &Bag) -> Vec<Bag> {
+            let Some(&(item_weight, item_value)) = self.items.get(next) else {
+                return vec![];
+            };
+            let mut children = vec![(next + 1, weight, value)];
+            if weight + item_weight <= self.capacity {
+                children.insert(0, (next + 1, weight + item_weight, value + item_value));
+            }
+            children
+        }
+
+        fn value(&self, &(_, _, value): &Bag) -> u32 {
+            value
+        }
+
+        fn upper_bound(&self, &(next, _, value): &Bag) -> u32 {
+            value
+                + self.items[next..]
+                    .iter()
+                    .map(|&(_, value)| value)
+                    .sum::<u32>()
+        }
+    }
+
+    #[test]
+    fn finds_the_best_value() {
+        let knapsack = Knapsack {
+            items: vec![(5, 10), (4, 40), (6, 30), (3, 50)],
+            capacity: 10,
+        };
+        let (best, metrics) = maximize(&knapsack, (0, 0, 0));
+        assert_eq!(best, 90);
+        assert!(metrics.pruned > 0);
+        // without pruning, every one of the 2^4 ways of picking is a leaf
+        assert!(metrics.expanded < 31);
+    }
+}
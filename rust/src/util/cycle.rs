@@ -0,0 +1,147 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{Add, Mul, Sub},
+};
+
+/// Where a simulation starts repeating itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first step that's part of the cycle
+    pub start: usize,
+    /// How many steps it takes to come back around
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step that's in the same state as step `n`
+    pub fn earliest_equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// What happened over the steps of a simulation, up to the point where it
+/// started repeating
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History<M> {
+    pub cycle: Option<Cycle>,
+    /// The metric after each step, starting from before the first one
+    metrics: Vec<M>,
+}
+
+impl<M> History<M>
+where
+    M: Copy + Add<Output = M> + Sub<Output = M> + Mul<Output = M> + TryFrom<usize>,
+{
+    /// What the metric would be after `n` steps, assuming that it goes up by
+    /// the same amount every time around the cycle
+    pub fn extrapolate(&self, n: usize) -> eyre::Result<M> {
+        if let Some(&metric) = self.metrics.get(n) {
+            return Ok(metric);
+        }
+
+        let cycle = self
+            .cycle
+            .ok_or_else(|| eyre::eyre!("the simulation stopped before step {n} without a cycle"))?;
+        let per_lap = self.metrics[cycle.start + cycle.length] - self.metrics[cycle.start];
+        let laps = M::try_from((n - cycle.start) / cycle.length)
+            .map_err(|_| eyre::eyre!("can't count the laps it takes to get to step {n}"))?;
+        Ok(self.metrics[cycle.earliest_equivalent(n)] + per_lap * laps)
+    }
+}
+
+impl<M> History<M> {
+    /// The metric after each step, starting from before the first one and
+    /// going up to the step that closed the cycle
+    pub fn into_metrics(self) -> Vec<M> {
+        self.metrics
+    }
+}
+
+/// Runs `step` on `state` until it gets back to a state that it's been in
+/// before, going by `fingerprint`, or until it's taken `max_steps` steps. The
+/// fingerprint has to capture everything that decides what happens next, or
+/// the cycle found won't really be one.
+pub fn detect<S, K, M>(
+    mut state: S,
+    max_steps: usize,
+    mut step: impl FnMut(&mut S),
+    mut fingerprint: impl FnMut(&S) -> K,
+    mut metric: impl FnMut(&S) -> M,
+) -> History<M>
+where
+    K: Eq + Hash,
+{
+    let mut seen = HashMap::new();
+    let mut metrics = Vec::new();
+    for n in 0.. {
+        metrics.push(metric(&state));
+        if let Some(start) = seen.insert(fingerprint(&state), n) {
+            log::debug!("found a cycle {} steps long from step {start}", n - start);
+            return History {
+                cycle: Some(Cycle {
+                    start,
+                    length: n - start,
+                }),
+                metrics,
+            };
+        }
+        if n == max_steps {
+            break;
+        }
+        step(&mut state);
+    }
+
+    History {
+        cycle: None,
+        metrics,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts 0, 1, then 2, 3, 4 over and over, keeping a running total
+    fn step((value, total): &mut (u32, u32)) {
+        *value = if *value == 4 { 2 } else { *value + 1 };
+        *total += *value;
+    }
+
+    #[test]
+    fn extrapolates_past_the_cycle() {
+        let history = detect(
+            (0, 0),
+            usize::MAX,
+            step,
+            |&(value, _)| value,
+            |&(_, total)| total,
+        );
+        assert_eq!(
+            history.cycle,
+            Some(Cycle {
+                start: 2,
+                length: 3
+            })
+        );
+
+        let mut state = (0, 0);
+        for n in 0..50 {
+            assert_eq!(history.extrapolate(n).unwrap(), state.1, "after {n} steps");
+            step(&mut state);
+        }
+    }
+
+    #[test]
+    fn stops_early_without_a_cycle() {
+        let history = detect(0_u64, 10, |n| *n += 1, |&n| n, |&n| n * 2);
+        assert_eq!(history.cycle, None);
+        assert_eq!(history.extrapolate(10).unwrap(), 20);
+        assert!(history.extrapolate(11).is_err());
+        assert_eq!(history.into_metrics().len(), 11);
+    }
+}
@@ -0,0 +1,149 @@
+use super::point::{Coordinate, Point2};
+
+/// One of the eight ways to step on a grid, with north being up (towards
+/// smaller `y`), like in the puzzles' maps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+use Direction::*;
+
+impl Direction {
+    /// Every direction, going clockwise from north
+    pub const ALL: [Self; 8] = [
+        North, NorthEast, East, SouthEast, South, SouthWest, West, NorthWest,
+    ];
+
+    /// The directions that don't go diagonally, going clockwise from north
+    pub const CARDINALS: [Self; 4] = [North, East, South, West];
+
+    /// How many eighths of a turn clockwise from north this is
+    fn eighths(self) -> u8 {
+        self as u8
+    }
+
+    fn from_eighths(eighths: u8) -> Self {
+        Self::ALL[usize::from(eighths % 8)]
+    }
+
+    pub fn is_cardinal(self) -> bool {
+        self.eighths().is_multiple_of(2)
+    }
+
+    /// A quarter turn clockwise
+    pub fn turn_right(self) -> Self {
+        Self::from_eighths(self.eighths() + 2)
+    }
+
+    /// A quarter turn anticlockwise
+    pub fn turn_left(self) -> Self {
+        Self::from_eighths(self.eighths() + 6)
+    }
+
+    pub fn turn_around(self) -> Self {
+        Self::from_eighths(self.eighths() + 4)
+    }
+
+    /// Where one step this way goes, with `y` going down
+    pub fn delta<T: Coordinate>(self) -> Point2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            North => Point2::new(zero, -one),
+            NorthEast => Point2::new(one, -one),
+            East => Point2::new(one, zero),
+            SouthEast => Point2::new(one, one),
+            South => Point2::new(zero, one),
+            SouthWest => Point2::new(-one, one),
+            West => Point2::new(-one, zero),
+            NorthWest => Point2::new(-one, -one),
+        }
+    }
+
+    /// Reads an arrow (`^v<>`), like the ones drawn on maps
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(North),
+            'v' => Some(South),
+            '<' => Some(West),
+            '>' => Some(East),
+            _ => None,
+        }
+    }
+
+    /// Reads an arrow (`^v<>`), a compass point (`NSEW`) or a step in a path
+    /// (`UDLR`)
+    // only arrows come up in the days so far, but other years' puzzles give
+    // directions as compass points or path steps
+    #[allow(dead_code)]
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'N' | 'U' => Some(North),
+            'S' | 'D' => Some(South),
+            'W' | 'L' => Some(West),
+            'E' | 'R' => Some(East),
+            _ => Self::from_arrow(c),
+        }
+    }
+
+    /// The character that points this way, `^v<>` for the cardinal directions
+    pub fn arrow(self) -> char {
+        match self {
+            North => '^',
+            NorthEast => '↗',
+            East => '>',
+            SouthEast => '↘',
+            South => 'v',
+            SouthWest => '↙',
+            West => '<',
+            NorthWest => '↖',
+        }
+    }
+
+    /// How Advent of Code scores facing this way: 0 for east and going up
+    /// clockwise from there. Diagonal directions don't have a score.
+    pub fn facing_score(self) -> Option<usize> {
+        self.is_cardinal()
+            .then(|| usize::from((self.eighths() + 6) % 8 / 2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.turn_around());
+            assert_eq!(direction.turn_around().delta::<i32>(), -direction.delta());
+        }
+        assert_eq!(North.turn_right(), East);
+        assert_eq!(North.turn_left(), West);
+        assert_eq!(SouthEast.turn_right(), SouthWest);
+    }
+
+    #[test]
+    fn parses_and_scores() {
+        for (chars, direction) in [("^NU", North), ("vSD", South), ("<WL", West), (">ER", East)] {
+            assert!(chars
+                .chars()
+                .all(|c| Direction::from_char(c) == Some(direction)));
+            assert_eq!(Direction::from_arrow(direction.arrow()), Some(direction));
+            assert_eq!(Direction::from_arrow(chars.chars().nth(1).unwrap()), None);
+        }
+        assert_eq!(Direction::from_char('.'), None);
+
+        let scores = [East, South, West, North].map(Direction::facing_score);
+        assert_eq!(scores, [Some(0), Some(1), Some(2), Some(3)]);
+        assert_eq!(NorthEast.facing_score(), None);
+    }
+}
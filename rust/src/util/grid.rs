@@ -0,0 +1,258 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+/// The offsets to the tiles above, to the right of, below and to the left of
+/// a tile
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The offsets to all 8 tiles around a tile, going clockwise from the one
+/// above it
+const SURROUNDING: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular map of tiles, indexed by `(x, y)` with `(0, 0)` in the top
+/// left corner
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    tiles: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parses a map drawn with one character per tile, where every line has
+    /// to be the same length
+    pub fn parse(input: &str, parse_tile: impl FnMut(char) -> Option<T>) -> eyre::Result<Self> {
+        Self::parse_lines(input, || None, parse_tile)
+    }
+
+    /// Parses a map whose lines can be different lengths, filling in the
+    /// tiles past the end of short lines with `padding`
+    pub fn parse_ragged(
+        input: &str,
+        padding: T,
+        parse_tile: impl FnMut(char) -> Option<T>,
+    ) -> eyre::Result<Self>
+    where
+        T: Clone,
+    {
+        Self::parse_lines(input, || Some(padding.clone()), parse_tile)
+    }
+
+    fn parse_lines(
+        input: &str,
+        mut padding: impl FnMut() -> Option<T>,
+        mut parse_tile: impl FnMut(char) -> Option<T>,
+    ) -> eyre::Result<Self> {
+        let lines: Vec<&str> = input.lines().collect();
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut tiles = Vec::with_capacity(width * lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            let mut line_width = 0;
+            for (x, tile) in line.chars().enumerate() {
+                let tile = parse_tile(tile).ok_or_else(|| {
+                    eyre::eyre!("unexpected `{tile}` at line {}, column {}", y + 1, x + 1)
+                })?;
+                tiles.push(tile);
+                line_width += 1;
+            }
+
+            for _ in line_width..width {
+                tiles.push(padding().ok_or_else(|| {
+                    eyre::eyre!(
+                        "line {} is {line_width} tiles wide, but the widest is {width}",
+                        y + 1
+                    )
+                })?);
+            }
+        }
+
+        Ok(Self {
+            width,
+            height: lines.len(),
+            tiles,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.tiles[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.tiles[y * self.width + x])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.tiles[(y * self.width)..((y + 1) * self.width)]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on 0, but a grid with no columns has no tiles anyway
+        self.tiles.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.tiles.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every tile along with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.tiles
+            .iter()
+            .enumerate()
+            .map(move |(i, tile)| ((i % width, i / width), tile))
+    }
+}
+
+// no day walks a `Grid` tile by tile yet (day 22 wraps around the edges of the
+// map itself), but these are what most map puzzles end up needing
+#[allow(dead_code)]
+impl<T> Grid<T> {
+    /// Moves `(dx, dy)` from `(x, y)`, if that stays inside the grid
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    /// Moves `(dx, dy)` from `(x, y)`, coming back around the opposite edge
+    /// when going off the grid
+    pub fn offset_wrapping(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> (usize, usize) {
+        (
+            (x as isize + dx).rem_euclid(self.width as isize) as usize,
+            (y as isize + dy).rem_euclid(self.height as isize) as usize,
+        )
+    }
+
+    /// The tiles above, to the right of, below and to the left of `(x, y)`
+    /// that are inside the grid
+    pub fn neighbors(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// All of the (up to 8) tiles around `(x, y)`, including diagonally
+    pub fn surrounding(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        SURROUNDING
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y).unwrap_or_else(|| {
+            panic!(
+                "({x}, {y}) is outside the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside the {width}x{height} grid"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for tile in row {
+                write!(f, "{tile}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_digits(input: &str) -> eyre::Result<Grid<u32>> {
+        Grid::parse(input, |tile| tile.to_digit(10))
+    }
+
+    #[test]
+    fn parses_and_renders() {
+        let grid = parse_digits("123\n456").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+
+        let error = parse_digits("12\n3x").unwrap_err();
+        assert_eq!(error.to_string(), "unexpected `x` at line 2, column 2");
+        assert!(parse_digits("12\n3").is_err());
+
+        let ragged = Grid::parse_ragged("#\n.#", ' ', Some).unwrap();
+        assert_eq!(ragged.to_string(), "# \n.#");
+    }
+
+    #[test]
+    fn finds_neighbors() {
+        let grid = parse_digits("123\n456\n789").unwrap();
+        let corner: Vec<_> = grid
+            .neighbors((0, 0))
+            .map(|position| grid[position])
+            .collect();
+        assert_eq!(corner, [2, 4]);
+        assert_eq!(grid.surrounding((1, 1)).count(), 8);
+        assert_eq!(grid.surrounding((2, 2)).count(), 3);
+
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset_wrapping((0, 0), (-1, 0)), (2, 0));
+        assert_eq!(grid.offset_wrapping((1, 2), (0, 4)), (1, 0));
+    }
+}
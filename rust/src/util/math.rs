@@ -0,0 +1,164 @@
+//! Number theory for puzzles that loop around. Anything that could overflow
+//! gives `None` instead, like the standard library's `checked_*` methods.
+
+/// The greatest common divisor, where `gcd(0, 0)` is 0
+pub const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, where anything's with 0 is 0
+pub const fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// The extended Euclidean algorithm, done in `i128` so that nothing along the
+/// way can overflow
+fn egcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The greatest common divisor `g` of `a` and `b` along with an `x` and `y`
+/// where `a * x + b * y == g`
+// `modinv` and `crt` call `egcd_wide` directly, so this is only for days that
+// want the coefficients themselves
+#[allow(dead_code)]
+pub fn egcd(a: i64, b: i64) -> Option<(i64, i64, i64)> {
+    let (g, x, y) = egcd_wide(a.into(), b.into());
+    Some((g.try_into().ok()?, x.try_into().ok()?, y.try_into().ok()?))
+}
+
+/// The `x` in `0..modulus` where `a * x` is 1 more than a multiple of
+/// `modulus`, if there is one (there is as long as they're coprime)
+// no day here does modular division, but it's a staple of later years
+#[allow(dead_code)]
+pub fn modinv(a: i64, modulus: i64) -> Option<i64> {
+    if modulus <= 0 {
+        return None;
+    }
+    let (g, x, _) = egcd_wide(a.into(), modulus.into());
+    if g != 1 {
+        return None;
+    }
+    x.rem_euclid(modulus.into()).try_into().ok()
+}
+
+/// `base` to the power of `exp`, modulo `modulus`, in `0..modulus`
+// for the puzzles with huge repeated shuffles, which none of these days are
+#[allow(dead_code)]
+pub fn modpow(base: i64, mut exp: u64, modulus: i64) -> Option<i64> {
+    if modulus <= 0 {
+        return None;
+    }
+    let modulus = i128::from(modulus);
+    let mut base = i128::from(base).rem_euclid(modulus);
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result.try_into().ok()
+}
+
+/// Solves a set of congruences `x ≡ residue (mod modulus)` using the Chinese
+/// remainder theorem, giving the smallest non-negative `x` and the modulus
+/// that every solution repeats with. The moduli don't have to be coprime, but
+/// if they aren't the congruences can contradict each other, which gives
+/// `None`.
+// for lining up things that repeat with different periods; day 24's blizzards
+// all start together, so `lcm` is enough there
+#[allow(dead_code)]
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (mut x, mut modulus) = (0_i128, 1_i128);
+    for (residue, next_modulus) in congruences {
+        if next_modulus <= 0 {
+            return None;
+        }
+        let next_modulus = i128::from(next_modulus);
+        let (g, inverse, _) = egcd_wide(modulus, next_modulus);
+        let difference = i128::from(residue) - x;
+        if difference % g != 0 {
+            return None;
+        }
+
+        // step x by the current modulus until it also fits the next
+        // congruence, which keeps it fitting all the previous ones
+        let step_modulus = next_modulus / g;
+        let steps = (difference / g % step_modulus * inverse).rem_euclid(step_modulus);
+        modulus = modulus.checked_mul(step_modulus)?;
+        x = (x + modulus / step_modulus * steps).rem_euclid(modulus);
+        i64::try_from(modulus).ok()?;
+    }
+    Some((x.try_into().ok()?, modulus.try_into().ok()?))
+}
+
+/// Wraps a signed index around a list `len` items long, so that `-1` is the
+/// last item. There's nothing to wrap around if `len` is 0.
+pub fn wrap(index: isize, len: usize) -> Option<usize> {
+    let len = isize::try_from(len).ok().filter(|&len| len > 0)?;
+    index.rem_euclid(len).try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_divisors_and_multiples() {
+        assert_eq!(gcd(150, 20), 10);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(lcm(150, 20), Some(300));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+
+        let (g, x, y) = egcd(240, 46).unwrap();
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+    }
+
+    #[test]
+    fn does_modular_arithmetic() {
+        assert_eq!(modinv(3, 11), Some(4));
+        assert_eq!(modinv(-3, 11), Some(7));
+        assert_eq!(modinv(4, 8), None);
+        assert_eq!(modpow(4, 13, 497), Some(445));
+        assert_eq!(modpow(-2, 3, 5), Some(2));
+        assert_eq!(modpow(i64::MAX, u64::MAX, i64::MAX - 1), Some(1));
+
+        assert_eq!(wrap(-1, 5), Some(4));
+        assert_eq!(wrap(12, 5), Some(2));
+        assert_eq!(wrap(3, 0), None);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // not coprime, but consistent
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(
+            crt([(0, i64::MAX), (1, i64::MAX - 1)]).map(|(_, m)| m),
+            None
+        );
+    }
+}
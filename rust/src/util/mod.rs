@@ -0,0 +1,12 @@
+//! Building blocks shared between the days' solutions
+
+pub mod bitgrid;
+pub mod branch_and_bound;
+pub mod cycle;
+pub mod direction;
+pub mod grid;
+pub mod math;
+pub mod ocr;
+pub mod parse;
+pub mod point;
+pub mod search;
@@ -0,0 +1,180 @@
+use super::grid::Grid;
+
+/// The block letters some puzzles spell their answers out in, each as it's
+/// drawn
+struct Font {
+    height: usize,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+/// The font that's 6 rows tall, which most puzzles use
+const SMALL: Font = Font {
+    height: 6,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+/// The font that's 10 rows tall
+// kept to a line per letter, since the rows don't line up either way
+#[rustfmt::skip]
+const LARGE: Font = Font {
+    height: 10,
+    glyphs: &[
+        ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+        ('B', &["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+        ('C', &[".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+        ('E', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('F', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('G', &[".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+        ('H', &["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+        ('J', &["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+        ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+        ('L', &["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+        ('P', &["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('R', &["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+        ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+        ('Z', &["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+    ],
+};
+
+/// Reads the letters drawn in `art` with `#`s (or `█`s) on a background of
+/// `.`s or spaces. The letters can be either of the fonts the puzzles use, and
+/// are told apart by the blank columns between them.
+pub fn recognize(art: &str) -> eyre::Result<String> {
+    let grid = Grid::parse_ragged(art, false, |c| match c {
+        '#' | '█' => Some(true),
+        '.' | ' ' => Some(false),
+        _ => None,
+    })?;
+
+    // the letters don't have to fill the whole drawing, so ignore any blank
+    // rows around them
+    let filled = |y: usize| grid.row(y).iter().any(|&lit| lit);
+    let top = (0..grid.height())
+        .find(|&y| filled(y))
+        .ok_or_else(|| eyre::eyre!("there aren't any letters in the drawing"))?;
+    let bottom = (0..grid.height()).rfind(|&y| filled(y)).unwrap_or(top);
+    let height = bottom - top + 1;
+    let font = [SMALL, LARGE]
+        .into_iter()
+        .find(|font| font.height == height)
+        .ok_or_else(|| {
+            eyre::eyre!("the letters are {height} rows tall, but the fonts are 6 and 10 rows tall")
+        })?;
+
+    let blank = |x: usize| (top..=bottom).all(|y| !grid[(x, y)]);
+    let mut text = String::new();
+    let mut x = 0;
+    while x < grid.width() {
+        if blank(x) {
+            x += 1;
+            continue;
+        }
+
+        let left = x;
+        while x < grid.width() && !blank(x) {
+            x += 1;
+        }
+        let glyph: Vec<String> = (top..=bottom)
+            .map(|y| {
+                grid.row(y)[left..x]
+                    .iter()
+                    .map(|&lit| if lit { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        let letter = font
+            .glyphs
+            .iter()
+            .find(|(_, drawn)| drawn.iter().eq(glyph.iter()))
+            .map(|&(letter, _)| letter)
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "unknown letter in columns {} to {}, rows {} to {}:\n{}",
+                    left + 1,
+                    x,
+                    top + 1,
+                    bottom + 1,
+                    glyph.join("\n")
+                )
+            })?;
+        text.push(letter);
+    }
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws `text` in `font` the way the puzzles do, with a blank column
+    /// between letters
+    fn draw(font: &Font, text: &str) -> String {
+        (0..font.height)
+            .map(|y| {
+                text.chars()
+                    .map(|letter| {
+                        let (_, glyph) = font.glyphs.iter().find(|(l, _)| *l == letter).unwrap();
+                        glyph[y]
+                    })
+                    .collect::<Vec<_>>()
+                    .join(".")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn reads_every_letter() {
+        for font in [SMALL, LARGE] {
+            let alphabet: String = font.glyphs.iter().map(|&(letter, _)| letter).collect();
+            assert_eq!(recognize(&draw(&font, &alphabet)).unwrap(), alphabet);
+        }
+    }
+
+    #[test]
+    fn reads_the_example_screen() {
+        // a screen like 2022 day 10's, with a blank column to its right
+        let screen = "\
+###..#..#.###...##..###..###...##..#..#.
+#..#.#..#.#..#.#..#.#..#.#..#.#..#.#..#.
+#..#.#..#.#..#.#....###..###..#..#.####.
+###..#..#.###..#....#..#.#..#.####.#..#.
+#....#..#.#.#..#..#.#..#.#..#.#..#.#..#.
+#.....##..#..#..##..###..###..#..#.#..#.";
+        assert_eq!(recognize(screen).unwrap(), "PURCBBAH");
+        assert_eq!(recognize(&screen.replace('.', " ")).unwrap(), "PURCBBAH");
+    }
+
+    #[test]
+    fn says_where_unknown_letters_are() {
+        let art = format!("\n{}", draw(&SMALL, "HI").replace("###\n", "#.#\n"));
+        let error = recognize(&art).unwrap_err().to_string();
+        assert!(
+            error.starts_with("unknown letter in columns 6 to 8, rows 2 to 7:\n#.#\n"),
+            "{error}"
+        );
+        assert!(recognize("#\n#").is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use std::{fmt::Display, panic::Location};
+
+use nom::{
+    bytes::complete::tag,
+    error::{context, ErrorKind, VerboseError, VerboseErrorKind},
+    Offset,
+};
+
+/// What the puzzles' nom parsers return, which keeps track of what they were
+/// expecting when they fail
+pub type Result<'a, T> = nom::IResult<&'a str, T, VerboseError<&'a str>>;
+
+/// Matches `expected` exactly, saying what it was looking for if it isn't
+/// there
+pub fn text<'a>(expected: &'static str) -> impl FnMut(&'a str) -> Result<'a, &'a str> {
+    context(expected, tag(expected))
+}
+
+/// A line of the input that couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The puzzle whose input this is, like `2022 day 19`
+    puzzle: Option<String>,
+    /// Which line it was, starting from 1
+    line_number: usize,
+    line: String,
+    /// How many characters into the line the problem is, starting from 0
+    column: usize,
+    expected: String,
+}
+
+impl std::error::Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(puzzle) = &self.puzzle {
+            write!(f, "{puzzle}: ")?;
+        }
+        writeln!(
+            f,
+            "can't parse line {}, column {}: expected {}",
+            self.line_number,
+            self.column + 1,
+            self.expected
+        )?;
+
+        let gutter = " ".repeat(self.line_number.to_string().len());
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line_number, self.line)?;
+        write!(f, "{gutter} | {}^", " ".repeat(self.column))
+    }
+}
+
+/// Works out which puzzle a source file is for from its path, e.g.
+/// `src/y2022/d19/p1.rs` is for 2022 day 19
+fn puzzle_from_path(path: &str) -> Option<String> {
+    let number = |component: &str, prefix| {
+        let number = component.strip_prefix(prefix)?;
+        (!number.is_empty() && number.bytes().all(|digit| digit.is_ascii_digit()))
+            .then(|| number.trim_start_matches('0').to_string())
+    };
+
+    let components: Vec<&str> = path.split(['/', '\\']).collect();
+    components.windows(2).find_map(|pair| {
+        Some(format!(
+            "{} day {}",
+            number(pair[0], 'y')?,
+            number(pair[1], 'd')?
+        ))
+    })
+}
+
+/// Puts what a nom error kind was looking for into words
+fn describe(kind: &VerboseErrorKind) -> String {
+    match kind {
+        VerboseErrorKind::Context(expected) => format!("{expected:?}"),
+        VerboseErrorKind::Char(expected) => format!("{:?}", expected.to_string()),
+        VerboseErrorKind::Nom(ErrorKind::Digit | ErrorKind::Float) => "a number".to_string(),
+        VerboseErrorKind::Nom(ErrorKind::Alpha) => "a letter".to_string(),
+        VerboseErrorKind::Nom(ErrorKind::AlphaNumeric) => "a letter or number".to_string(),
+        VerboseErrorKind::Nom(ErrorKind::Space | ErrorKind::MultiSpace) => "a space".to_string(),
+        VerboseErrorKind::Nom(ErrorKind::OneOf) => "one of a few characters".to_string(),
+        VerboseErrorKind::Nom(ErrorKind::Eof) => "more of the line".to_string(),
+        VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+    }
+}
+
+fn parse_line<'a, T>(
+    puzzle: Option<String>,
+    index: usize,
+    line: &'a str,
+    mut parser: impl FnMut(&'a str) -> Result<'a, T>,
+) -> eyre::Result<T> {
+    let line = line.trim_end();
+    let error = |remaining: &str, expected| ParseError {
+        puzzle: puzzle.clone(),
+        line_number: index + 1,
+        line: line.to_string(),
+        column: line[..line.offset(remaining)].chars().count(),
+        expected,
+    };
+
+    match parser(line) {
+        Ok(("", parsed)) => Ok(parsed),
+        Ok((remaining, _)) => Err(error(remaining, "the end of the line".to_string()).into()),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+            // the first error is the innermost one, so it's where the problem
+            // actually is, but a context further out says more about what
+            // should have been there
+            let (remaining, kind) = err
+                .errors
+                .first()
+                .ok_or_else(|| eyre::eyre!("the parser failed without saying why"))?;
+            let expected = err
+                .errors
+                .iter()
+                .take_while(|(input, _)| input.as_ptr() == remaining.as_ptr())
+                .find(|(_, kind)| matches!(kind, VerboseErrorKind::Context(_)))
+                .map_or(kind, |(_, kind)| kind);
+            Err(error(remaining, describe(expected)).into())
+        }
+        Err(nom::Err::Incomplete(_)) => Err(error("", "more of the line".to_string()).into()),
+    }
+}
+
+/// Parses one line of the input (the `index`th, counting from 0), making sure
+/// that all of it gets used
+#[track_caller]
+pub fn line<'a, T>(
+    index: usize,
+    line: &'a str,
+    parser: impl FnMut(&'a str) -> Result<'a, T>,
+) -> eyre::Result<T> {
+    let puzzle = puzzle_from_path(Location::caller().file());
+    parse_line(puzzle, index, line, parser)
+}
+
+/// Parses every line of the input with the same parser
+#[track_caller]
+pub fn lines<'a, T>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> Result<'a, T>,
+) -> eyre::Result<Vec<T>> {
+    let puzzle = puzzle_from_path(Location::caller().file());
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse_line(puzzle.clone(), index, line, &mut parser))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{character::complete::u32, sequence::separated_pair};
+
+    use super::*;
+
+    fn pair(input: &str) -> Result<'_, (u32, u32)> {
+        separated_pair(u32, text(" and "), u32)(input)
+    }
+
+    #[test]
+    fn points_at_the_problem() {
+        assert_eq!(lines("1 and 2\n3 and 4", pair).unwrap(), [(1, 2), (3, 4)]);
+
+        let error = lines("1 and 2\n3 or 4", pair).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "can't parse line 2, column 2: expected \" and \"\n  |\n2 | 3 or 4\n  |  ^"
+        );
+
+        let error = line(9, "1 and x", pair).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("line 10, column 7: expected a number"));
+
+        let error = line(0, "1 and 2 and 3", pair).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("column 8: expected the end of the line"));
+    }
+
+    #[test]
+    fn finds_the_puzzle() {
+        assert_eq!(
+            puzzle_from_path("src/y2022/d19/p1.rs"),
+            Some("2022 day 19".to_string())
+        );
+        assert_eq!(
+            puzzle_from_path("src\\y2021\\d05\\mod.rs"),
+            Some("2021 day 5".to_string())
+        );
+        assert_eq!(puzzle_from_path("src/util/parse.rs"), None);
+    }
+}
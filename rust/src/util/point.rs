@@ -0,0 +1,244 @@
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use itertools::iproduct;
+
+/// A signed integer that points can be made of
+pub trait Coordinate:
+    Copy
+    + Debug
+    + Default
+    + Ord
+    + Hash
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_coordinate!(i8, i16, i32, i64, i128, isize);
+
+/// A position (or the difference between two) on a 2D grid, with `y` going
+/// down like in the puzzles' maps
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A position (or the difference between two) in 3D space
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// Implements the operators and helpers that work the same way for every
+/// number of dimensions
+macro_rules! impl_point {
+    ($point:ident { $($field:ident),+ }) => {
+        // both point types get every helper, but each day only uses a few of
+        // them for one of the types
+        #[allow(dead_code)]
+        impl<T: Coordinate> $point<T> {
+            pub const fn new($($field: T),+) -> Self {
+                Self { $($field),+ }
+            }
+
+            /// A point with every coordinate set to `value`
+            pub const fn splat(value: T) -> Self {
+                Self { $($field: value),+ }
+            }
+
+            /// The number of orthogonal steps it takes to get to `other`
+            pub fn manhattan(self, other: Self) -> T {
+                T::ZERO $(+ (self.$field - other.$field).abs())+
+            }
+
+            /// The number of steps it takes to get to `other` when diagonal
+            /// steps are allowed too
+            pub fn chebyshev(self, other: Self) -> T {
+                T::ZERO $(.max((self.$field - other.$field).abs()))+
+            }
+
+            /// The smallest of each coordinate out of the two points
+            pub fn min(self, other: Self) -> Self {
+                Self { $($field: self.$field.min(other.$field)),+ }
+            }
+
+            /// The largest of each coordinate out of the two points
+            pub fn max(self, other: Self) -> Self {
+                Self { $($field: self.$field.max(other.$field)),+ }
+            }
+
+            /// The opposite corners of the smallest box that contains all of
+            /// `points`, or `None` if there aren't any
+            pub fn bounding_box(points: impl IntoIterator<Item = Self>) -> Option<(Self, Self)> {
+                points.into_iter().fold(None, |bounds, point| match bounds {
+                    Some((min, max)) => Some((point.min(min), point.max(max))),
+                    None => Some((point, point)),
+                })
+            }
+
+            /// Whether the point is inside the box with the corners `min` and
+            /// `max` (inclusive)
+            pub fn within(self, min: Self, max: Self) -> bool {
+                true $(&& (min.$field..=max.$field).contains(&self.$field))+
+            }
+
+            /// The points one step away along a single axis
+            pub fn neighbors(self) -> impl Iterator<Item = Self> {
+                let axes = [$(Self { $field: T::ONE, ..Self::default() }),+];
+                axes.into_iter()
+                    .flat_map(move |axis| [self - axis, self + axis])
+            }
+
+            /// The points one step away, including diagonally
+            pub fn surrounding(self) -> impl Iterator<Item = Self> {
+                Self::offsets()
+                    .filter(|&offset| offset != Self::default())
+                    .map(move |offset| self + offset)
+            }
+        }
+
+        impl<T: Coordinate> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self::Output {
+                Self { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl<T: Coordinate> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Coordinate> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self::Output {
+                Self { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl<T: Coordinate> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: Coordinate> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: Coordinate> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, scale: T) -> Self::Output {
+                Self { $($field: self.$field * scale),+ }
+            }
+        }
+    };
+}
+
+impl_point!(Point2 { x, y });
+impl_point!(Point3 { x, y, z });
+
+/// -1, 0 and 1
+fn steps<T: Coordinate>() -> [T; 3] {
+    [-T::ONE, T::ZERO, T::ONE]
+}
+
+impl<T: Coordinate> Point2<T> {
+    /// Every combination of -1, 0 and 1
+    fn offsets() -> impl Iterator<Item = Self> {
+        iproduct!(steps(), steps()).map(|(y, x)| Self::new(x, y))
+    }
+}
+
+impl<T: Coordinate> Point3<T> {
+    /// Every combination of -1, 0 and 1
+    fn offsets() -> impl Iterator<Item = Self> {
+        iproduct!(steps(), steps(), steps()).map(|(z, y, x)| Self::new(x, y, z))
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Point3::new(1, -2, 3);
+        let b = Point3::new(4, 0, -1);
+        assert_eq!(a + b, Point3::new(5, -2, 2));
+        assert_eq!(a - b, Point3::new(-3, -2, 4));
+        assert_eq!(-a * 2, Point3::new(-2, 4, -6));
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a.chebyshev(b), 4);
+    }
+
+    #[test]
+    fn finds_neighbors() {
+        let origin = Point2::new(0, 0);
+        assert_eq!(origin.neighbors().count(), 4);
+        assert_eq!(origin.surrounding().count(), 8);
+        assert!(origin.neighbors().all(|point| point.manhattan(origin) == 1));
+        assert_eq!(Point3::new(0, 0, 0).neighbors().count(), 6);
+        assert_eq!(Point3::new(0, 0, 0).surrounding().count(), 26);
+    }
+
+    #[test]
+    fn finds_bounding_boxes() {
+        let points = [Point2::new(3, -1), Point2::new(-2, 4), Point2::new(0, 0)];
+        let (min, max) = Point2::bounding_box(points).unwrap();
+        assert_eq!((min, max), (Point2::new(-2, -1), Point2::new(3, 4)));
+        assert!(points.iter().all(|point| point.within(min, max)));
+        assert!(!Point2::new(4, 0).within(min, max));
+        assert_eq!(Point2::<i32>::bounding_box([]), None);
+    }
+}
@@ -0,0 +1,277 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    fmt::Display,
+    hash::Hash,
+    ops::Add,
+};
+
+/// How much work a search did, for profiling
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// How many states had their successors looked at
+    pub expanded: usize,
+    /// How many distinct states were seen, including ones never expanded
+    pub seen: usize,
+    /// The most states that were waiting to be expanded at once
+    pub max_frontier: usize,
+}
+
+impl Display for Metrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expanded {} states, saw {}, with at most {} waiting",
+            self.expanded, self.seen, self.max_frontier
+        )
+    }
+}
+
+/// How a search went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome<S, C> {
+    /// The cost of getting to the goal and every state along the way (from
+    /// the start to the goal), if a goal could be reached
+    pub found: Option<(C, Vec<S>)>,
+    pub metrics: Metrics,
+}
+
+impl<S, C: Copy> Outcome<S, C> {
+    pub fn cost(&self) -> Option<C> {
+        self.found.as_ref().map(|&(cost, _)| cost)
+    }
+
+    // the days so far only need the cost, but the path is what to look at
+    // when a search finds the wrong one
+    #[allow(dead_code)]
+    pub fn path(&self) -> Option<&[S]> {
+        self.found.as_ref().map(|(_, path)| path.as_slice())
+    }
+}
+
+/// Follows the states back to the start, the start being the one with no
+/// parent
+fn path_to<S: Clone + Eq + Hash>(goal: S, parents: &HashMap<S, Option<S>>) -> Vec<S> {
+    let mut path = vec![goal];
+    while let Some(Some(parent)) = parents.get(path.last().expect("the path starts non-empty")) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Finds the fewest steps from `start` to a state where `is_goal` is true.
+/// Each state's successors are only asked for once, so a search without a
+/// goal visits everything reachable from `start` exactly once.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> eyre::Result<I>,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> eyre::Result<Outcome<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut metrics = Metrics::default();
+    let mut parents = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((state, steps)) = queue.pop_front() {
+        if is_goal(&state) {
+            metrics.seen = parents.len();
+            let path = path_to(state, &parents);
+            return Ok(Outcome {
+                found: Some((steps, path)),
+                metrics,
+            });
+        }
+
+        metrics.expanded += 1;
+        for next in successors(&state)? {
+            if let Entry::Vacant(entry) = parents.entry(next.clone()) {
+                entry.insert(Some(state.clone()));
+                queue.push_back((next, steps + 1));
+            }
+        }
+        metrics.max_frontier = metrics.max_frontier.max(queue.len());
+    }
+
+    metrics.seen = parents.len();
+    Ok(Outcome {
+        found: None,
+        metrics,
+    })
+}
+
+/// Finds the cheapest way from `start` to a state where `is_goal` is true,
+/// where `successors` gives each next state along with the cost of getting to
+/// it
+// no day has steps with different costs yet
+#[allow(dead_code)]
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> eyre::Result<I>,
+    is_goal: impl FnMut(&S) -> bool,
+) -> eyre::Result<Outcome<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but looks at the states that `heuristic` thinks are
+/// closest to a goal first. The heuristic must never overestimate the
+/// remaining cost, or the path found might not be the cheapest.
+// day 24's search is cheap enough as a plain BFS that a heuristic doesn't pay
+// for itself
+#[allow(dead_code)]
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> eyre::Result<I>,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> eyre::Result<Outcome<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut metrics = Metrics::default();
+    // the cheapest known cost of getting to each state, and where from
+    let mut best = HashMap::from([(start.clone(), (C::default(), None))]);
+    // the heap only holds indices into `states` so that states don't have to
+    // be `Ord`; the index also breaks ties in the order states were found
+    let mut states = vec![start.clone()];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let state = states[index].clone();
+        if best[&state].0 < cost {
+            // this state was found more cheaply after it was queued
+            continue;
+        }
+        if is_goal(&state) {
+            metrics.seen = best.len();
+            let parents = best
+                .into_iter()
+                .map(|(state, (_, parent))| (state, parent))
+                .collect();
+            let path = path_to(state, &parents);
+            return Ok(Outcome {
+                found: Some((cost, path)),
+                metrics,
+            });
+        }
+
+        metrics.expanded += 1;
+        for (next, step_cost) in successors(&state)? {
+            let next_cost = cost + step_cost;
+            match best.entry(next.clone()) {
+                Entry::Occupied(entry) if entry.get().0 <= next_cost => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert((next_cost, Some(state.clone())));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((next_cost, Some(state.clone())));
+                }
+            }
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                states.len(),
+            )));
+            states.push(next);
+        }
+        metrics.max_frontier = metrics.max_frontier.max(heap.len());
+    }
+
+    metrics.seen = best.len();
+    Ok(Outcome {
+        found: None,
+        metrics,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::grid::Grid;
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#G..
+.##...#.";
+
+    fn maze() -> (Grid<char>, (usize, usize), (usize, usize)) {
+        let maze = Grid::parse(MAZE, Some).unwrap();
+        let find = |tile| maze.iter().find(|&(_, &t)| t == tile).unwrap().0;
+        let (start, goal) = (find('S'), find('G'));
+        (maze, start, goal)
+    }
+
+    #[test]
+    fn finds_shortest_paths() {
+        let (maze, start, goal) = maze();
+        let open = |&position: &(usize, usize)| {
+            Ok(maze
+                .neighbors(position)
+                .filter(|&next| maze[next] != '#')
+                .collect::<Vec<_>>())
+        };
+
+        let bfs = bfs(start, open, |&position| position == goal).unwrap();
+        assert_eq!(bfs.cost(), Some(9));
+        let path = bfs.path().unwrap();
+        assert_eq!((path[0], path[9]), (start, goal));
+        assert!(path.windows(2).all(|step| {
+            let ((x1, y1), (x2, y2)) = (step[0], step[1]);
+            x1.abs_diff(x2) + y1.abs_diff(y2) == 1
+        }));
+
+        let weighted = |position: &(usize, usize)| {
+            Ok(open(position)?
+                .into_iter()
+                .map(|next| (next, 1))
+                .collect::<Vec<_>>())
+        };
+        let dijkstra = dijkstra(start, weighted, |&position| position == goal).unwrap();
+        assert_eq!(dijkstra.cost(), Some(9));
+
+        let distance = |&(x, y): &(usize, usize)| x.abs_diff(goal.0) + y.abs_diff(goal.1);
+        let astar = astar(start, weighted, distance, |&position| position == goal).unwrap();
+        assert_eq!(astar.cost(), Some(9));
+        assert!(astar.metrics.expanded <= dijkstra.metrics.expanded);
+    }
+
+    #[test]
+    fn prefers_cheaper_paths_to_shorter_ones() {
+        // going straight from 0 to 2 costs 10, going through 1 costs 2
+        let edges = |&node: &u32| {
+            Ok(match node {
+                0 => vec![(2, 10), (1, 1)],
+                1 => vec![(2, 1)],
+                _ => vec![],
+            })
+        };
+        let outcome = dijkstra(0, edges, |&node| node == 2).unwrap();
+        assert_eq!(outcome.found, Some((2, vec![0, 1, 2])));
+    }
+
+    #[test]
+    fn visits_everything_without_a_goal() {
+        let (maze, start, _) = maze();
+        let outcome = bfs(
+            start,
+            |&position| Ok(maze.neighbors(position).filter(|&next| maze[next] != '#')),
+            |_| false,
+        )
+        .unwrap();
+        assert_eq!(outcome.found, None);
+        let open = maze.iter().filter(|&(_, &tile)| tile != '#').count();
+        assert_eq!(outcome.metrics.expanded, open);
+        assert_eq!(outcome.metrics.seen, open);
+    }
+}
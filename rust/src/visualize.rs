@@ -0,0 +1,182 @@
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    terminal::{self, ClearType},
+};
+
+/// A simulation that can be stepped through and drawn in the terminal
+pub trait Visualize {
+    /// Renders the current state as text
+    fn frame(&self) -> String;
+
+    /// Advances the simulation by one step, returning `false` (and leaving the
+    /// state alone) once there's nothing left to do
+    fn step(&mut self) -> bool;
+}
+
+const MIN_DELAY: Duration = Duration::from_millis(1);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// Parses a speed in steps per second
+pub fn parse_speed(speed: &str) -> Result<f64, String> {
+    let parsed = speed
+        .trim()
+        .parse::<f64>()
+        .map_err(|err| format!("can't parse `{speed}` as a number: {err}"))?;
+    check_speed(parsed)
+}
+
+/// Checks that a speed is a positive, finite number of steps per second
+pub fn check_speed(speed: f64) -> Result<f64, String> {
+    if speed.is_finite() && speed > 0.0 {
+        Ok(speed)
+    } else {
+        Err(format!(
+            "the speed has to be a positive number, not {speed}"
+        ))
+    }
+}
+
+/// How long to show each step for at a given speed
+fn delay(steps_per_second: f64) -> Result<Duration, String> {
+    let seconds = 1.0 / check_speed(steps_per_second)?;
+    let delay = Duration::try_from_secs_f64(seconds).map_err(|err| err.to_string())?;
+    Ok(delay.clamp(MIN_DELAY, MAX_DELAY))
+}
+
+/// Switches to raw mode on the alternate screen, and switches back when
+/// dropped so that the terminal is restored even if we bail out early
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn draw(frame: &str, status: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    queue!(
+        stdout,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(ClearType::All)
+    )?;
+    // raw mode doesn't move back to the start of the line on `\n`
+    for line in frame.lines() {
+        write!(stdout, "{line}\r\n")?;
+    }
+    write!(stdout, "\r\n{status}")?;
+    stdout.flush()
+}
+
+/// Plays a simulation back in the terminal until it finishes or the user quits
+///
+/// Space pauses, `n` steps once while paused, `+`/`-` change the speed and
+/// `q` quits.
+pub fn play(simulation: &mut dyn Visualize, steps_per_second: f64) -> eyre::Result<()> {
+    let mut delay = delay(steps_per_second).map_err(|err| eyre::eyre!(err))?;
+    let _guard = TerminalGuard::new()?;
+
+    let mut paused = false;
+    let mut finished = false;
+    let mut steps = 0;
+
+    loop {
+        let state = if finished {
+            "finished"
+        } else if paused {
+            "paused"
+        } else {
+            "playing"
+        };
+        let status = format!(
+            "step {steps} | {state} | {:.1} steps/s | [space] pause  [n] step  [+/-] speed  [q] quit",
+            1.0 / delay.as_secs_f64()
+        );
+        draw(&simulation.frame(), &status)?;
+
+        let deadline = Instant::now() + delay;
+        let mut should_step = false;
+        loop {
+            let has_event = if paused || finished {
+                true
+            } else {
+                event::poll(deadline.saturating_duration_since(Instant::now()))?
+            };
+            if !has_event {
+                should_step = true;
+                break;
+            }
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(())
+                }
+                KeyCode::Char(' ') => {
+                    paused = !paused;
+                    break;
+                }
+                KeyCode::Char('n') | KeyCode::Right if paused => {
+                    should_step = true;
+                    break;
+                }
+                KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Up => {
+                    delay = (delay / 2).max(MIN_DELAY);
+                    break;
+                }
+                KeyCode::Char('-') | KeyCode::Down => {
+                    delay = (delay * 2).min(MAX_DELAY);
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        if should_step && !finished {
+            if simulation.step() {
+                steps += 1;
+            } else {
+                finished = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_bad_speeds() {
+        assert_eq!(parse_speed("2.5"), Ok(2.5));
+        assert_eq!(delay(4.0), Ok(Duration::from_millis(250)));
+        assert_eq!(delay(0.01), Ok(MAX_DELAY));
+        for speed in ["0", "-1", "NaN", "inf", "fast"] {
+            assert!(parse_speed(speed).is_err(), "{speed}");
+        }
+        assert!(delay(0.0).is_err());
+        assert!(delay(f64::MIN_POSITIVE / 4.0).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use std::{fs, path::Path, thread, time::Duration};
+
+use crate::{params::Params, registry::Solver, runner};
+
+/// How often the watched file is checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Strips the trailing newlines that editors like to leave at the end of files
+pub fn trim_input(input: &str) -> &str {
+    input.trim_end_matches(['\r', '\n'])
+}
+
+/// Keeps track of the last answer so that reruns can say whether it changed
+#[derive(Debug, Default)]
+struct Watcher {
+    previous: Option<String>,
+}
+
+impl Watcher {
+    /// Solves the input again, describing how it went
+    fn rerun(&mut self, solver: Solver, input: &str, params: &Params) -> String {
+        let (answer, elapsed) = match runner::time(solver, input, params) {
+            Ok((answer, elapsed)) => (answer.to_string(), elapsed),
+            // a half-written example shouldn't stop the watching
+            Err(err) => return format!("failed: {err}"),
+        };
+
+        let change = match self.previous.replace(answer.clone()) {
+            None => String::new(),
+            Some(previous) if previous == answer => " (unchanged)".to_string(),
+            Some(previous) => format!(" (changed from {previous})"),
+        };
+        format!("{answer} (took {elapsed:?}){change}")
+    }
+}
+
+/// Reruns a solver every time the file at `path` changes, until killed
+pub fn watch(path: &Path, solver: Solver, params: &Params, label: &str) -> eyre::Result<()> {
+    println!(
+        "Watching {} for changes, press Ctrl-C to stop",
+        path.display()
+    );
+
+    let mut watcher = Watcher::default();
+    let mut last_input = None;
+    loop {
+        // the file can briefly go missing while an editor saves it
+        if let Ok(input) = fs::read_to_string(path) {
+            if last_input.as_ref() != Some(&input) {
+                let result = watcher.rerun(solver, trim_input(&input), params);
+                println!("The solution for {label} is {result}");
+                last_input = Some(input);
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{registry, Day, Part, Year};
+
+    #[test]
+    fn reports_changes() {
+        let solver = registry::solver(Year::Y2022, Day::D25, Part::Part1).unwrap();
+        let params = Params::default();
+        let mut watcher = Watcher::default();
+
+        assert!(watcher
+            .rerun(solver, "1\n2", &params)
+            .starts_with("1= (took "));
+        assert!(watcher
+            .rerun(solver, "2\n1", &params)
+            .ends_with("(unchanged)"));
+        assert!(watcher
+            .rerun(solver, "2\n2", &params)
+            .ends_with("(changed from 1=)"));
+    }
+}
@@ -1,101 +1,60 @@
-use std::{
-    collections::{HashMap, HashSet},
-    ops::{Add, AddAssign, Sub, SubAssign},
+use std::collections::{HashMap, HashSet};
+
+use nom::{
+    character::complete::i64,
+    combinator::map,
+    sequence::{preceded, tuple},
+};
+
+use crate::util::{
+    parse::{self, text},
+    point::Point3,
 };
 
 pub mod p1;
 pub mod p2;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Offset(i64, i64, i64);
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Beacon(i64, i64, i64);
-
-impl Beacon {
-    fn fingerprint(self, other: Self) -> u64 {
-        let Self(x1, y1, z1) = self;
-        let Self(x2, y2, z2) = other;
-        x1.abs_diff(x2) + y1.abs_diff(y2) + z1.abs_diff(z2)
-    }
-
-    fn rotation_iter() -> impl Iterator<Item = fn(Beacon) -> Beacon> {
-        static ITER: [fn(Beacon) -> Beacon; 24] = [
-            |Beacon(x, y, z)| Beacon(x, y, z),
-            |Beacon(x, y, z)| Beacon(x, z, -y),
-            |Beacon(x, y, z)| Beacon(x, -y, -z),
-            |Beacon(x, y, z)| Beacon(x, -z, y),
-            |Beacon(x, y, z)| Beacon(-x, -y, z),
-            |Beacon(x, y, z)| Beacon(-x, z, y),
-            |Beacon(x, y, z)| Beacon(-x, y, -z),
-            |Beacon(x, y, z)| Beacon(-x, -z, -y),
-            |Beacon(x, y, z)| Beacon(y, z, x),
-            |Beacon(x, y, z)| Beacon(y, x, -z),
-            |Beacon(x, y, z)| Beacon(y, -z, -x),
-            |Beacon(x, y, z)| Beacon(y, -x, z),
-            |Beacon(x, y, z)| Beacon(-y, -z, x),
-            |Beacon(x, y, z)| Beacon(-y, x, z),
-            |Beacon(x, y, z)| Beacon(-y, z, -x),
-            |Beacon(x, y, z)| Beacon(-y, -x, -z),
-            |Beacon(x, y, z)| Beacon(z, x, y),
-            |Beacon(x, y, z)| Beacon(z, y, -x),
-            |Beacon(x, y, z)| Beacon(z, -x, -y),
-            |Beacon(x, y, z)| Beacon(z, -y, x),
-            |Beacon(x, y, z)| Beacon(-z, -x, y),
-            |Beacon(x, y, z)| Beacon(-z, y, x),
-            |Beacon(x, y, z)| Beacon(-z, x, -y),
-            |Beacon(x, y, z)| Beacon(-z, -y, -x),
-        ];
-        ITER.iter().copied()
-    }
-}
-
-impl Add<Offset> for Beacon {
-    type Output = Beacon;
-
-    fn add(self, offset: Offset) -> Self::Output {
-        let Beacon(x1, y1, z1) = self;
-        let Offset(x2, y2, z2) = offset;
-        Beacon(x1 + x2, y1 + y2, z1 + z2)
-    }
-}
-
-impl AddAssign<Offset> for Beacon {
-    fn add_assign(&mut self, offset: Offset) {
-        *self = *self + offset
-    }
-}
-
-impl Sub<Offset> for Beacon {
-    type Output = Beacon;
-
-    fn sub(self, offset: Offset) -> Self::Output {
-        let Beacon(x1, y1, z1) = self;
-        let Offset(x2, y2, z2) = offset;
-        Beacon(x1 - x2, y1 - y2, z1 - z2)
-    }
-}
-
-impl Sub<Beacon> for Beacon {
-    type Output = Offset;
-
-    fn sub(self, other: Self) -> Self::Output {
-        let Beacon(x1, y1, z1) = self;
-        let Beacon(x2, y2, z2) = other;
-        Offset(x1 - x2, y1 - y2, z1 - z2)
-    }
-}
-
-impl SubAssign<Offset> for Beacon {
-    fn sub_assign(&mut self, offset: Offset) {
-        *self = *self - offset;
-    }
+/// Where a scanner is relative to the first one
+type Offset = Point3<i64>;
+
+type Beacon = Point3<i64>;
+
+/// Every way a scanner could be facing, as a way to turn the beacons it sees
+/// to face the same way as the first scanner
+fn rotations() -> impl Iterator<Item = fn(Beacon) -> Beacon> {
+    static ITER: [fn(Beacon) -> Beacon; 24] = [
+        |Point3 { x, y, z }| Beacon::new(x, y, z),
+        |Point3 { x, y, z }| Beacon::new(x, z, -y),
+        |Point3 { x, y, z }| Beacon::new(x, -y, -z),
+        |Point3 { x, y, z }| Beacon::new(x, -z, y),
+        |Point3 { x, y, z }| Beacon::new(-x, -y, z),
+        |Point3 { x, y, z }| Beacon::new(-x, z, y),
+        |Point3 { x, y, z }| Beacon::new(-x, y, -z),
+        |Point3 { x, y, z }| Beacon::new(-x, -z, -y),
+        |Point3 { x, y, z }| Beacon::new(y, z, x),
+        |Point3 { x, y, z }| Beacon::new(y, x, -z),
+        |Point3 { x, y, z }| Beacon::new(y, -z, -x),
+        |Point3 { x, y, z }| Beacon::new(y, -x, z),
+        |Point3 { x, y, z }| Beacon::new(-y, -z, x),
+        |Point3 { x, y, z }| Beacon::new(-y, x, z),
+        |Point3 { x, y, z }| Beacon::new(-y, z, -x),
+        |Point3 { x, y, z }| Beacon::new(-y, -x, -z),
+        |Point3 { x, y, z }| Beacon::new(z, x, y),
+        |Point3 { x, y, z }| Beacon::new(z, y, -x),
+        |Point3 { x, y, z }| Beacon::new(z, -x, -y),
+        |Point3 { x, y, z }| Beacon::new(z, -y, x),
+        |Point3 { x, y, z }| Beacon::new(-z, -x, y),
+        |Point3 { x, y, z }| Beacon::new(-z, y, x),
+        |Point3 { x, y, z }| Beacon::new(-z, x, -y),
+        |Point3 { x, y, z }| Beacon::new(-z, -y, -x),
+    ];
+    ITER.iter().copied()
 }
 
 #[derive(Debug, Clone)]
 struct Scanner {
     beacons: Vec<Beacon>,
-    fingerprints: HashMap<u64, usize>,
+    fingerprints: HashMap<i64, usize>,
 }
 
 impl Scanner {
@@ -105,7 +64,7 @@ impl Scanner {
             for j in (i + 1)..beacons.len() {
                 let beacon1 = beacons[i];
                 let beacon2 = beacons[j];
-                let fingerprint = beacon1.fingerprint(beacon2);
+                let fingerprint = beacon1.manhattan(beacon2);
                 *fingerprints.entry(fingerprint).or_insert(0) += 1;
             }
         }
@@ -134,24 +93,24 @@ impl Scanner {
         for other_beacon in other_beacons {
             for &my_beacon in &self.beacons {
                 let offset = my_beacon - other_beacon;
-                if offset == Offset(68, -1246, -43) {
-                    dbg!(&my_beacon);
-                    dbg!(&other_beacon);
-                }
                 *offsets.entry(offset).or_insert(0_usize) += 1;
             }
         }
 
-        offsets
+        let offset = offsets
             .into_iter()
-            .find_map(|(offset, count)| (count >= 12).then_some(offset))
+            .find_map(|(offset, count)| (count >= 12).then_some((offset, count)));
+        if let Some((offset, count)) = offset {
+            log::trace!("{count} beacons line up at {offset:?}");
+        }
+        offset.map(|(offset, _)| offset)
     }
 
     fn try_transform_to_find_offset(
         &self,
         potential_solved_scanner: &mut Scanner,
     ) -> Option<Offset> {
-        for transform in Beacon::rotation_iter() {
+        for transform in rotations() {
             if let Some(offset) = self.offset(
                 potential_solved_scanner
                     .beacons
@@ -170,11 +129,18 @@ impl Scanner {
     }
 }
 
+fn parse_beacon(input: &str) -> parse::Result<'_, Beacon> {
+    map(
+        tuple((i64, preceded(text(","), i64), preceded(text(","), i64))),
+        Beacon::from,
+    )(input)
+}
+
 fn parse_scanners(input: &str) -> eyre::Result<Vec<Scanner>> {
     let mut scanners = Vec::new();
     let mut cur_beacons = Vec::new();
 
-    for line in input.lines() {
+    for (index, line) in input.lines().enumerate() {
         let line = line.trim();
 
         if line.is_empty() {
@@ -187,18 +153,7 @@ fn parse_scanners(input: &str) -> eyre::Result<Vec<Scanner>> {
             continue;
         }
 
-        use nom::{
-            character::complete::{char, i64},
-            sequence::{preceded, tuple},
-        };
-        let (_, (x, y, z)) = tuple::<&str, _, nom::error::Error<_>, _>((
-            i64,
-            preceded(char(','), i64),
-            preceded(char(','), i64),
-        ))(line)
-        .map_err(|err| eyre::eyre!("parsing error: {err:?}"))?;
-
-        cur_beacons.push(Beacon(x, y, z));
+        cur_beacons.push(parse::line(index, line, parse_beacon)?);
     }
 
     scanners.rotate_left(1);
@@ -218,7 +173,7 @@ fn solve_scanners(mut scanners: Vec<Scanner>) -> eyre::Result<(Vec<Offset>, Hash
         }
         None => return Ok((Vec::new(), HashSet::new())),
     };
-    let mut offsets = vec![Offset(0, 0, 0)];
+    let mut offsets = vec![Offset::default()];
 
     let mut cur_ref_scanner_index = 0;
     let mut cur_ref_scanner;
@@ -260,123 +215,129 @@ mod tests {
     #[test]
     fn it_works() {
         let scanner0 = Scanner::new(vec![
-            Beacon(404, -588, -901),
-            Beacon(528, -643, 409),
-            Beacon(-838, 591, 734),
-            Beacon(390, -675, -793),
-            Beacon(-537, -823, -458),
-            Beacon(-485, -357, 347),
-            Beacon(-345, -311, 381),
-            Beacon(-661, -816, -575),
-            Beacon(-876, 649, 763),
-            Beacon(-618, -824, -621),
-            Beacon(553, 345, -567),
-            Beacon(474, 580, 667),
-            Beacon(-447, -329, 318),
-            Beacon(-584, 868, -557),
-            Beacon(544, -627, -890),
-            Beacon(564, 392, -477),
-            Beacon(455, 729, 728),
-            Beacon(-892, 524, 684),
-            Beacon(-689, 845, -530),
-            Beacon(423, -701, 434),
-            Beacon(7, -33, -71),
-            Beacon(630, 319, -379),
-            Beacon(443, 580, 662),
-            Beacon(-789, 900, -551),
-            Beacon(459, -707, 401),
+            Beacon::new(404, -588, -901),
+            Beacon::new(528, -643, 409),
+            Beacon::new(-838, 591, 734),
+            Beacon::new(390, -675, -793),
+            Beacon::new(-537, -823, -458),
+            Beacon::new(-485, -357, 347),
+            Beacon::new(-345, -311, 381),
+            Beacon::new(-661, -816, -575),
+            Beacon::new(-876, 649, 763),
+            Beacon::new(-618, -824, -621),
+            Beacon::new(553, 345, -567),
+            Beacon::new(474, 580, 667),
+            Beacon::new(-447, -329, 318),
+            Beacon::new(-584, 868, -557),
+            Beacon::new(544, -627, -890),
+            Beacon::new(564, 392, -477),
+            Beacon::new(455, 729, 728),
+            Beacon::new(-892, 524, 684),
+            Beacon::new(-689, 845, -530),
+            Beacon::new(423, -701, 434),
+            Beacon::new(7, -33, -71),
+            Beacon::new(630, 319, -379),
+            Beacon::new(443, 580, 662),
+            Beacon::new(-789, 900, -551),
+            Beacon::new(459, -707, 401),
         ]);
 
         let mut scanner1 = Scanner::new(vec![
-            Beacon(686, 422, 578),
-            Beacon(605, 423, 415),
-            Beacon(515, 917, -361),
-            Beacon(-336, 658, 858),
-            Beacon(95, 138, 22),
-            Beacon(-476, 619, 847),
-            Beacon(-340, -569, -846),
-            Beacon(567, -361, 727),
-            Beacon(-460, 603, -452),
-            Beacon(669, -402, 600),
-            Beacon(729, 430, 532),
-            Beacon(-500, -761, 534),
-            Beacon(-322, 571, 750),
-            Beacon(-466, -666, -811),
-            Beacon(-429, -592, 574),
-            Beacon(-355, 545, -477),
-            Beacon(703, -491, -529),
-            Beacon(-328, -685, 520),
-            Beacon(413, 935, -424),
-            Beacon(-391, 539, -444),
-            Beacon(586, -435, 557),
-            Beacon(-364, -763, -893),
-            Beacon(807, -499, -711),
-            Beacon(755, -354, -619),
-            Beacon(553, 889, -390),
+            Beacon::new(686, 422, 578),
+            Beacon::new(605, 423, 415),
+            Beacon::new(515, 917, -361),
+            Beacon::new(-336, 658, 858),
+            Beacon::new(95, 138, 22),
+            Beacon::new(-476, 619, 847),
+            Beacon::new(-340, -569, -846),
+            Beacon::new(567, -361, 727),
+            Beacon::new(-460, 603, -452),
+            Beacon::new(669, -402, 600),
+            Beacon::new(729, 430, 532),
+            Beacon::new(-500, -761, 534),
+            Beacon::new(-322, 571, 750),
+            Beacon::new(-466, -666, -811),
+            Beacon::new(-429, -592, 574),
+            Beacon::new(-355, 545, -477),
+            Beacon::new(703, -491, -529),
+            Beacon::new(-328, -685, 520),
+            Beacon::new(413, 935, -424),
+            Beacon::new(-391, 539, -444),
+            Beacon::new(586, -435, 557),
+            Beacon::new(-364, -763, -893),
+            Beacon::new(807, -499, -711),
+            Beacon::new(755, -354, -619),
+            Beacon::new(553, 889, -390),
         ]);
 
-        println!("{}", scanner0.could_overlap(&scanner1));
-        println!("{:?}", scanner0.try_transform_to_find_offset(&mut scanner1));
+        assert!(scanner0.could_overlap(&scanner1));
+        assert_eq!(
+            scanner0.try_transform_to_find_offset(&mut scanner1),
+            Some(Offset::new(68, -1246, -43))
+        );
     }
 
     #[test]
     fn test2() {
         let scanners = vec![
             Scanner::new(vec![
-                Beacon(686, 422, 578),
-                Beacon(605, 423, 415),
-                Beacon(515, 917, -361),
-                Beacon(-336, 658, 858),
-                Beacon(95, 138, 22),
-                Beacon(-476, 619, 847),
-                Beacon(-340, -569, -846),
-                Beacon(567, -361, 727),
-                Beacon(-460, 603, -452),
-                Beacon(669, -402, 600),
-                Beacon(729, 430, 532),
-                Beacon(-500, -761, 534),
-                Beacon(-322, 571, 750),
-                Beacon(-466, -666, -811),
-                Beacon(-429, -592, 574),
-                Beacon(-355, 545, -477),
-                Beacon(703, -491, -529),
-                Beacon(-328, -685, 520),
-                Beacon(413, 935, -424),
-                Beacon(-391, 539, -444),
-                Beacon(586, -435, 557),
-                Beacon(-364, -763, -893),
-                Beacon(807, -499, -711),
-                Beacon(755, -354, -619),
-                Beacon(553, 889, -390),
+                Beacon::new(686, 422, 578),
+                Beacon::new(605, 423, 415),
+                Beacon::new(515, 917, -361),
+                Beacon::new(-336, 658, 858),
+                Beacon::new(95, 138, 22),
+                Beacon::new(-476, 619, 847),
+                Beacon::new(-340, -569, -846),
+                Beacon::new(567, -361, 727),
+                Beacon::new(-460, 603, -452),
+                Beacon::new(669, -402, 600),
+                Beacon::new(729, 430, 532),
+                Beacon::new(-500, -761, 534),
+                Beacon::new(-322, 571, 750),
+                Beacon::new(-466, -666, -811),
+                Beacon::new(-429, -592, 574),
+                Beacon::new(-355, 545, -477),
+                Beacon::new(703, -491, -529),
+                Beacon::new(-328, -685, 520),
+                Beacon::new(413, 935, -424),
+                Beacon::new(-391, 539, -444),
+                Beacon::new(586, -435, 557),
+                Beacon::new(-364, -763, -893),
+                Beacon::new(807, -499, -711),
+                Beacon::new(755, -354, -619),
+                Beacon::new(553, 889, -390),
             ]),
             Scanner::new(vec![
-                Beacon(404, -588, -901),
-                Beacon(528, -643, 409),
-                Beacon(-838, 591, 734),
-                Beacon(390, -675, -793),
-                Beacon(-537, -823, -458),
-                Beacon(-485, -357, 347),
-                Beacon(-345, -311, 381),
-                Beacon(-661, -816, -575),
-                Beacon(-876, 649, 763),
-                Beacon(-618, -824, -621),
-                Beacon(553, 345, -567),
-                Beacon(474, 580, 667),
-                Beacon(-447, -329, 318),
-                Beacon(-584, 868, -557),
-                Beacon(544, -627, -890),
-                Beacon(564, 392, -477),
-                Beacon(455, 729, 728),
-                Beacon(-892, 524, 684),
-                Beacon(-689, 845, -530),
-                Beacon(423, -701, 434),
-                Beacon(7, -33, -71),
-                Beacon(630, 319, -379),
-                Beacon(443, 580, 662),
-                Beacon(-789, 900, -551),
-                Beacon(459, -707, 401),
+                Beacon::new(404, -588, -901),
+                Beacon::new(528, -643, 409),
+                Beacon::new(-838, 591, 734),
+                Beacon::new(390, -675, -793),
+                Beacon::new(-537, -823, -458),
+                Beacon::new(-485, -357, 347),
+                Beacon::new(-345, -311, 381),
+                Beacon::new(-661, -816, -575),
+                Beacon::new(-876, 649, 763),
+                Beacon::new(-618, -824, -621),
+                Beacon::new(553, 345, -567),
+                Beacon::new(474, 580, 667),
+                Beacon::new(-447, -329, 318),
+                Beacon::new(-584, 868, -557),
+                Beacon::new(544, -627, -890),
+                Beacon::new(564, 392, -477),
+                Beacon::new(455, 729, 728),
+                Beacon::new(-892, 524, 684),
+                Beacon::new(-689, 845, -530),
+                Beacon::new(423, -701, 434),
+                Beacon::new(7, -33, -71),
+                Beacon::new(630, 319, -379),
+                Beacon::new(443, 580, 662),
+                Beacon::new(-789, 900, -551),
+                Beacon::new(459, -707, 401),
             ]),
         ];
-        dbg!(solve_scanners(scanners));
+        let (offsets, beacons) = solve_scanners(scanners).unwrap();
+        assert_eq!(offsets, [Offset::default(), Offset::new(68, -1246, -43)]);
+        // the two scanners see 25 beacons each, 12 of them the same
+        assert_eq!(beacons.len(), 38);
     }
 }
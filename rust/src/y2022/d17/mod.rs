@@ -1,3 +1,7 @@
+use rand::{rngs::StdRng, Rng};
+
+use crate::{generate::InputGenerator, visualize::Visualize};
+
 pub mod p1;
 pub mod p2;
 
@@ -9,6 +13,10 @@ const PIECES: [([u8; 4], usize, usize); 5] = [
     ([0x30, 0x30, 0, 0], 2, 2),
 ];
 
+/// The bits of a row that are the chamber's seven columns, with the leftmost
+/// column in the highest bit. The lowest bit isn't part of the chamber.
+const COLUMNS: u8 = 0xfe;
+
 #[derive(Debug, Clone)]
 struct Board<'a> {
     board: Vec<u8>,
@@ -113,7 +121,150 @@ impl<'a> Board<'a> {
     fn total_height(&self) -> usize {
         self.max_height + self.num_rows_trimmed
     }
+
+    /// The empty spaces that can still be reached from above the tower, row
+    /// by row going down from the top. Nothing else can affect where later
+    /// pieces land, so along with the next piece and jet, this decides how the
+    /// tower grows from here.
+    fn surface(&self) -> Vec<u8> {
+        let depth = self.max_height.min(self.num_rows_to_keep);
+        let air = |row: usize| !self.board[self.max_height - 1 - row] & COLUMNS;
+        // spreads sideways through a row from the spaces in `reachable`,
+        // without going past either wall
+        let spread = |mut reachable: u8, air: u8| loop {
+            let spread = (reachable | reachable << 1 | reachable >> 1) & air & COLUMNS;
+            if spread == reachable {
+                return reachable;
+            }
+            reachable = spread;
+        };
+
+        // the air can wind back up as well as down, so keep sweeping down and
+        // up until there's nowhere new to get to
+        let mut surface = vec![0; depth];
+        loop {
+            let previous = surface.clone();
+            let mut above = COLUMNS;
+            for (row, reachable) in surface.iter_mut().enumerate() {
+                *reachable = spread(*reachable | above & air(row), air(row));
+                above = *reachable;
+            }
+            for row in (0..depth.saturating_sub(1)).rev() {
+                surface[row] = spread(surface[row] | surface[row + 1] & air(row), air(row));
+            }
+            if surface == previous {
+                break;
+            }
+        }
+
+        while surface.last() == Some(&0) {
+            surface.pop();
+        }
+        surface
+    }
+
+    /// Everything that decides how the tower grows from here, so that the
+    /// same fingerprint coming up twice means the tower is repeating itself
+    fn fingerprint(&self) -> (usize, usize, Vec<u8>) {
+        (self.piece_index, self.direction_index, self.surface())
+    }
 }
 
 const BOARD_SIZE: usize = 4096;
 const NUM_ROWS_TO_KEEP: usize = 1024;
+
+/// Drops pieces one at a time so that the tower can be watched as it grows
+pub struct Simulation<'a> {
+    board: Board<'a>,
+    pieces_remaining: usize,
+}
+
+impl<'a> Simulation<'a> {
+    fn new(directions: &'a [u8], num_pieces: usize) -> Self {
+        Self {
+            board: Board::new(BOARD_SIZE, NUM_ROWS_TO_KEEP, directions),
+            pieces_remaining: num_pieces,
+        }
+    }
+}
+
+impl Visualize for Simulation<'_> {
+    fn frame(&self) -> String {
+        const ROWS_SHOWN: usize = 40;
+
+        let board = &self.board;
+        let mut frame = format!("height: {}\n", board.total_height());
+        // leave some room above the tower for the next piece
+        let top = (board.max_height + 3).min(board.board_size);
+        for row in board.board[top.saturating_sub(ROWS_SHOWN)..top]
+            .iter()
+            .rev()
+        {
+            frame.push('|');
+            frame.extend((0..7).map(|x| if row & (0x80 >> x) != 0 { '#' } else { '.' }));
+            frame.push_str("|\n");
+        }
+        if top <= ROWS_SHOWN && board.num_rows_trimmed == 0 {
+            frame.push_str("+-------+\n");
+        }
+        frame
+    }
+
+    fn step(&mut self) -> bool {
+        if self.pieces_remaining == 0 {
+            return false;
+        }
+        self.board.drop_piece();
+        self.pieces_remaining -= 1;
+        true
+    }
+}
+
+/// Generates a jet pattern `size` jets long
+pub struct Generator;
+
+impl InputGenerator for Generator {
+    fn generate(&self, rng: &mut StdRng, size: usize) -> String {
+        (0..size.max(1))
+            .map(|_| if rng.gen() { '<' } else { '>' })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A board whose rows, from the bottom up, are `rows`
+    fn board_with(rows: &[u8]) -> Board<'static> {
+        let mut board = Board::new(16, 8, b"<");
+        board.board[..rows.len()].copy_from_slice(rows);
+        board.max_height = rows.len();
+        board
+    }
+
+    #[test]
+    fn surface_stops_at_full_rows() {
+        assert_eq!(board_with(&[COLUMNS; 3]).surface(), Vec::<u8>::new());
+        assert_eq!(board_with(&[COLUMNS, 0x02]).surface(), [0xfc]);
+    }
+
+    #[test]
+    fn fingerprint_sees_the_left_column() {
+        let walled = board_with(&[COLUMNS, 0x80]);
+        let open = board_with(&[COLUMNS, 0]);
+        assert_eq!(walled.surface(), [0x7e]);
+        assert_ne!(walled.fingerprint(), open.fingerprint());
+    }
+
+    #[test]
+    fn frames_pieces_against_the_walls() {
+        for (jets, row) in [(b"<<<<", "|####...|"), (b">>>>", "|...####|")] {
+            let mut simulation = Simulation::new(jets, 1);
+            simulation.step();
+            let expected =
+                format!("height: 1\n|.......|\n|.......|\n|.......|\n{row}\n+-------+\n");
+            assert_eq!(simulation.frame(), expected);
+        }
+    }
+}
@@ -1,7 +1,23 @@
-use super::{Board, BOARD_SIZE, NUM_ROWS_TO_KEEP};
+use super::{Board, Simulation, BOARD_SIZE, NUM_ROWS_TO_KEEP};
+use crate::params::{Param, Params};
 
-pub fn solve(input: &str) -> eyre::Result<usize> {
+const PIECES: Param = Param {
+    name: "pieces",
+    default: 2022,
+    help: "how many rocks fall",
+};
+
+pub const PARAMS: &[Param] = &[PIECES];
+
+pub fn solve(input: &str, params: &Params) -> eyre::Result<usize> {
     let mut board = Board::new(BOARD_SIZE, NUM_ROWS_TO_KEEP, input.trim().as_bytes());
-    board.drop_pieces(2022);
+    board.drop_pieces(params.get(&PIECES)?);
     Ok(board.total_height())
 }
+
+pub fn visualize<'a>(input: &'a str, params: &Params) -> eyre::Result<Simulation<'a>> {
+    Ok(Simulation::new(
+        input.trim().as_bytes(),
+        params.get(&PIECES)?,
+    ))
+}
@@ -1,27 +1,88 @@
 use std::collections::HashSet;
 
+use itertools::Itertools;
 use nom::{
-    bytes::complete::tag,
+    combinator::map,
     sequence::{preceded, tuple},
-    IResult,
+};
+use rand::{rngs::StdRng, Rng};
+
+use crate::{
+    generate::InputGenerator,
+    util::{
+        parse::{self, text},
+        point::Point3,
+    },
 };
 
 pub mod p1;
 pub mod p2;
 
-fn parse_cube(input: &str) -> IResult<&str, (i32, i32, i32)> {
+fn parse_cube(input: &str) -> parse::Result<'_, Point3<i32>> {
     use nom::character::complete::i32;
 
-    tuple((i32, preceded(tag(","), i32), preceded(tag(","), i32)))(input)
+    map(
+        tuple((i32, preceded(text(","), i32), preceded(text(","), i32))),
+        Point3::from,
+    )(input)
+}
+
+fn get_cubes(input: &str) -> eyre::Result<HashSet<Point3<i32>>> {
+    Ok(parse::lines(input, parse_cube)?.into_iter().collect())
+}
+
+/// Generates a droplet made of `size` distinct cubes
+pub struct Generator;
+
+impl InputGenerator for Generator {
+    fn generate(&self, rng: &mut StdRng, size: usize) -> String {
+        // leave about as much air as lava so that there are pockets to find
+        let side = (2.0 * size as f64).cbrt().ceil().max(1.0) as i32;
+
+        let mut seen = HashSet::new();
+        let mut cubes = Vec::with_capacity(size);
+        while cubes.len() < size {
+            let cube = (
+                rng.gen_range(0..side),
+                rng.gen_range(0..side),
+                rng.gen_range(0..side),
+            );
+            if seen.insert(cube) {
+                cubes.push(cube);
+            }
+        }
+
+        cubes
+            .into_iter()
+            .map(|(x, y, z)| format!("{x},{y},{z}"))
+            .join("\n")
+    }
 }
 
-fn get_cubes(input: &str) -> eyre::Result<HashSet<(i32, i32, i32)>> {
-    input
-        .lines()
-        .map(|line| {
-            let (_, coords) =
-                parse_cube(line.trim()).map_err(|err| eyre::eyre!("can't parse coords: {err}"))?;
-            Ok(coords)
-        })
-        .collect()
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn parses_rendered_cubes(x: i32, y: i32, z: i32) {
+            let rendered = format!("{x},{y},{z}");
+            prop_assert_eq!(parse_cube(&rendered), Ok(("", Point3::new(x, y, z))));
+        }
+
+        #[test]
+        fn exterior_surface_is_at_most_total_surface(
+            cubes in prop::collection::hash_set((0..6, 0..6, 0..6), 1..80),
+        ) {
+            let input = cubes
+                .iter()
+                .map(|(x, y, z)| format!("{x},{y},{z}"))
+                .join("\n");
+            let total = p1::solve(&input).unwrap();
+            let exterior = p2::solve(&input).unwrap();
+            prop_assert!(i64::from(exterior) <= i64::from(total));
+        }
+    }
 }
@@ -1,56 +1,33 @@
-use std::collections::{HashSet, VecDeque};
+use to_method::To;
 
 use super::get_cubes;
-
-const STEPS: [(i32, i32, i32); 6] = [
-    (-1, 0, 0),
-    (1, 0, 0),
-    (0, -1, 0),
-    (0, 1, 0),
-    (0, 0, -1),
-    (0, 0, 1),
-];
+use crate::util::{point::Point3, search};
 
 pub fn solve(input: &str) -> eyre::Result<u32> {
     let cubes = get_cubes(input)?;
+    let Some((min, max)) = Point3::bounding_box(cubes.iter().copied()) else {
+        return Ok(0);
+    };
 
-    let (min_x, max_x, min_y, max_y, min_z, max_z) = cubes.iter().copied().fold(
-        (i32::MAX, i32::MIN, i32::MAX, i32::MIN, i32::MAX, i32::MIN),
-        |(min_x, max_x, min_y, max_y, min_z, max_z), (x, y, z)| {
-            (
-                min_x.min(x),
-                max_x.max(x),
-                min_y.min(y),
-                max_y.max(y),
-                min_z.min(z),
-                max_z.max(z),
-            )
-        },
-    );
+    // leave a layer of air all the way around so that the steam can get to
+    // every side of the droplet
+    let (min, max) = (min - Point3::splat(1), max + Point3::splat(1));
 
-    let mut visited = HashSet::new();
-    let x_range = (min_x - 1)..=(max_x + 1);
-    let y_range = (min_y - 1)..=(max_y + 1);
-    let z_range = (min_z - 1)..=(max_z + 1);
+    // the search looks around each tile of air once, counting the faces of
+    // lava it finds there and carrying on through the rest of the air
     let mut num_exposed_faces = 0;
-    let mut queue = VecDeque::from([(min_x - 1, min_y - 1, min_z - 1)]);
-    while let Some((x, y, z)) = queue.pop_front() {
-        if !visited.contains(&(x, y, z)) {
-            visited.insert((x, y, z));
-            for (dx, dy, dz) in STEPS {
-                if x_range.contains(&(x + dx))
-                    && y_range.contains(&(y + dy))
-                    && z_range.contains(&(z + dz))
-                {
-                    if cubes.contains(&(x + dx, y + dy, z + dz)) {
-                        num_exposed_faces += 1;
-                    } else if !visited.contains(&(x + dx, y + dy, z + dz)) {
-                        queue.push_back((x + dx, y + dy, z + dz));
-                    }
-                }
-            }
-        }
-    }
+    search::bfs(
+        min,
+        |&air| {
+            let (lava, air): (Vec<_>, Vec<_>) = air
+                .neighbors()
+                .filter(|neighbor| neighbor.within(min, max))
+                .partition(|neighbor| cubes.contains(neighbor));
+            num_exposed_faces += lava.len();
+            Ok(air)
+        },
+        |_| false,
+    )?;
 
-    Ok(num_exposed_faces)
+    Ok(num_exposed_faces.try_to()?)
 }
@@ -1,13 +1,19 @@
-use nom::{
-    bytes::complete::tag,
-    sequence::{delimited, separated_pair},
-    IResult,
+use itertools::Itertools;
+use nom::sequence::{delimited, separated_pair};
+use rand::{rngs::StdRng, Rng};
+
+use crate::{
+    generate::InputGenerator,
+    util::{
+        branch_and_bound::{self, Problem},
+        parse::{self, text},
+    },
 };
 
 pub mod p1;
 pub mod p2;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Blueprint {
     id: u32,
     ore_robot_cost: u32,
@@ -16,20 +22,21 @@ struct Blueprint {
     geode_robot_cost: (u32, u32),
 }
 
-fn parse_blueprint(input: &str) -> IResult<&str, Blueprint> {
+fn parse_blueprint(input: &str) -> parse::Result<'_, Blueprint> {
     use nom::character::complete::u32;
-    let (rem, id) = delimited(tag("Blueprint "), u32, tag(": "))(input)?;
-    let (rem, ore_robot_cost) = delimited(tag("Each ore robot costs "), u32, tag(" ore. "))(rem)?;
-    let (rem, clay_robot_cost) = delimited(tag("Each clay robot costs "), u32, tag(" ore. "))(rem)?;
+    let (rem, id) = delimited(text("Blueprint "), u32, text(": "))(input)?;
+    let (rem, ore_robot_cost) = delimited(text("Each ore robot costs "), u32, text(" ore. "))(rem)?;
+    let (rem, clay_robot_cost) =
+        delimited(text("Each clay robot costs "), u32, text(" ore. "))(rem)?;
     let (rem, obsidian_robot_cost) = delimited(
-        tag("Each obsidian robot costs "),
-        separated_pair(u32, tag(" ore and "), u32),
-        tag(" clay. "),
+        text("Each obsidian robot costs "),
+        separated_pair(u32, text(" ore and "), u32),
+        text(" clay. "),
     )(rem)?;
     let (rem, geode_robot_cost) = delimited(
-        tag("Each geode robot costs "),
-        separated_pair(u32, tag(" ore and "), u32),
-        tag(" obsidian."),
+        text("Each geode robot costs "),
+        separated_pair(u32, text(" ore and "), u32),
+        text(" obsidian."),
     )(rem)?;
     Ok((
         rem,
@@ -42,3 +49,213 @@ fn parse_blueprint(input: &str) -> IResult<&str, Blueprint> {
         },
     ))
 }
+
+/// Where things stand at the start of a minute
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Stockpile {
+    time_remaining: u32,
+    ore: u32,
+    ore_robots: u32,
+    clay: u32,
+    clay_robots: u32,
+    obsidian: u32,
+    obsidian_robots: u32,
+    geode_robots: u32,
+    /// The geodes opened so far
+    geodes: u32,
+}
+
+impl Stockpile {
+    /// What there'll be after a minute of collecting (not counting whatever
+    /// robot gets built during it)
+    fn collect(&self) -> Self {
+        Self {
+            time_remaining: self.time_remaining - 1,
+            ore: self.ore + self.ore_robots,
+            clay: self.clay + self.clay_robots,
+            obsidian: self.obsidian + self.obsidian_robots,
+            geodes: self.geodes + self.geode_robots,
+            ..*self
+        }
+    }
+}
+
+/// Running a factory to a blueprint, choosing which robot (if any) to build
+/// each minute
+struct Factory {
+    blueprint: Blueprint,
+    // there's no point in having more of a kind of robot than it takes of its
+    // resource to build any robot, since only one robot can be built a minute
+    max_ore_robots: u32,
+    max_clay_robots: u32,
+    max_obsidian_robots: u32,
+}
+
+impl Factory {
+    fn new(blueprint: Blueprint) -> Self {
+        Self {
+            blueprint,
+            max_ore_robots: blueprint
+                .ore_robot_cost
+                .max(blueprint.clay_robot_cost)
+                .max(blueprint.obsidian_robot_cost.0)
+                .max(blueprint.geode_robot_cost.0),
+            max_clay_robots: blueprint.obsidian_robot_cost.1,
+            max_obsidian_robots: blueprint.geode_robot_cost.1,
+        }
+    }
+
+    /// The most geodes the blueprint can open in `minutes`
+    fn max_geodes_opened(&self, minutes: u32) -> u32 {
+        let start = Stockpile {
+            time_remaining: minutes,
+            ore: 0,
+            ore_robots: 1,
+            clay: 0,
+            clay_robots: 0,
+            obsidian: 0,
+            obsidian_robots: 0,
+            geode_robots: 0,
+            geodes: 0,
+        };
+        let (max_geodes, metrics) = branch_and_bound::maximize(self, start);
+        log::debug!("blueprint {}: {metrics}", self.blueprint.id);
+        max_geodes
+    }
+}
+
+impl Problem for Factory {
+    type State = Stockpile;
+    type Value = u32;
+
+    fn children(&self, stockpile: &Stockpile) -> Vec<Stockpile> {
+        if stockpile.time_remaining == 0 {
+            return vec![];
+        }
+
+        let blueprint = &self.blueprint;
+        let next = stockpile.collect();
+        let mut children = Vec::with_capacity(5);
+        if stockpile.ore >= blueprint.geode_robot_cost.0
+            && stockpile.obsidian >= blueprint.geode_robot_cost.1
+        {
+            children.push(Stockpile {
+                ore: next.ore - blueprint.geode_robot_cost.0,
+                obsidian: next.obsidian - blueprint.geode_robot_cost.1,
+                geode_robots: next.geode_robots + 1,
+                ..next
+            });
+        }
+        if stockpile.obsidian_robots < self.max_obsidian_robots
+            && stockpile.ore >= blueprint.obsidian_robot_cost.0
+            && stockpile.clay >= blueprint.obsidian_robot_cost.1
+        {
+            children.push(Stockpile {
+                ore: next.ore - blueprint.obsidian_robot_cost.0,
+                clay: next.clay - blueprint.obsidian_robot_cost.1,
+                obsidian_robots: next.obsidian_robots + 1,
+                ..next
+            });
+        }
+        if stockpile.ore_robots < self.max_ore_robots && stockpile.ore >= blueprint.ore_robot_cost {
+            children.push(Stockpile {
+                ore: next.ore - blueprint.ore_robot_cost,
+                ore_robots: next.ore_robots + 1,
+                ..next
+            });
+        }
+        if stockpile.clay_robots < self.max_clay_robots
+            && stockpile.ore >= blueprint.clay_robot_cost
+        {
+            children.push(Stockpile {
+                ore: next.ore - blueprint.clay_robot_cost,
+                clay_robots: next.clay_robots + 1,
+                ..next
+            });
+        }
+        children.push(next);
+        children
+    }
+
+    /// The geodes that will have been opened by the end if no more geode
+    /// robots get built
+    fn value(&self, stockpile: &Stockpile) -> u32 {
+        stockpile.geodes + stockpile.geode_robots * stockpile.time_remaining
+    }
+
+    /// As if a geode robot could be built every minute from now on
+    fn upper_bound(&self, stockpile: &Stockpile) -> u32 {
+        let time = stockpile.time_remaining;
+        self.value(stockpile) + time * time.saturating_sub(1) / 2
+    }
+}
+
+/// Generates `size` blueprints with costs in the same ranges as the real input
+pub struct Generator;
+
+impl InputGenerator for Generator {
+    fn generate(&self, rng: &mut StdRng, size: usize) -> String {
+        (1..=size)
+            .map(|id| {
+                format!(
+                    "Blueprint {id}: \
+                     Each ore robot costs {} ore. \
+                     Each clay robot costs {} ore. \
+                     Each obsidian robot costs {} ore and {} clay. \
+                     Each geode robot costs {} ore and {} obsidian.",
+                    rng.gen_range(2..=4),
+                    rng.gen_range(2..=4),
+                    rng.gen_range(2..=4),
+                    rng.gen_range(5..=20),
+                    rng.gen_range(2..=4),
+                    rng.gen_range(5..=20),
+                )
+            })
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn render(blueprint: &Blueprint) -> String {
+        format!(
+            "Blueprint {}: \
+             Each ore robot costs {} ore. \
+             Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. \
+             Each geode robot costs {} ore and {} obsidian.",
+            blueprint.id,
+            blueprint.ore_robot_cost,
+            blueprint.clay_robot_cost,
+            blueprint.obsidian_robot_cost.0,
+            blueprint.obsidian_robot_cost.1,
+            blueprint.geode_robot_cost.0,
+            blueprint.geode_robot_cost.1,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn parses_rendered_blueprints(
+            id: u32,
+            ore_robot_cost: u32,
+            clay_robot_cost: u32,
+            obsidian_robot_cost: (u32, u32),
+            geode_robot_cost: (u32, u32),
+        ) {
+            let blueprint = Blueprint {
+                id,
+                ore_robot_cost,
+                clay_robot_cost,
+                obsidian_robot_cost,
+                geode_robot_cost,
+            };
+            let rendered = render(&blueprint);
+            prop_assert_eq!(parse_blueprint(&rendered), Ok(("", blueprint)));
+        }
+    }
+}
@@ -1,2 +1,135 @@
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng};
+
+use crate::{generate::InputGenerator, util::math};
+
 pub mod p1;
 pub mod p2;
+
+/// Mixes the list `rounds` times, moving each number (in the order they were
+/// originally in) forwards or backwards by its own value
+fn mix(list: &mut [isize], rounds: usize) -> eyre::Result<()> {
+    let mut indexes = (0..list.len()).collect::<Vec<_>>();
+    let len_minus_1 = list.len() - 1;
+    for _ in 0..rounds {
+        for indexes_index in 0..indexes.len() {
+            let og_idx = indexes[indexes_index];
+            let delta = list[og_idx];
+            if delta == 0 {
+                continue;
+            }
+            let raw_new_idx = delta.checked_add_unsigned(og_idx).ok_or_else(|| {
+                eyre::eyre!("can't add og_idx to delta (og_idx = {og_idx}, delta = {delta})")
+            })?;
+            // moving past either end is the same as moving from the other, so
+            // there are only `len - 1` places to end up in
+            let mut new_idx = math::wrap(raw_new_idx, len_minus_1).ok_or_else(|| {
+                eyre::eyre!("can't wrap raw_new_idx around the list (raw_new_idx = {raw_new_idx})")
+            })?;
+            if new_idx == 0 {
+                new_idx = len_minus_1;
+            }
+
+            match og_idx.cmp(&new_idx) {
+                std::cmp::Ordering::Less => {
+                    list[og_idx..=new_idx].rotate_left(1);
+                    for index in &mut indexes {
+                        if *index == og_idx {
+                            *index = new_idx;
+                        } else if og_idx < *index && *index <= new_idx {
+                            *index -= 1;
+                        }
+                    }
+                }
+                std::cmp::Ordering::Equal => continue,
+                std::cmp::Ordering::Greater => {
+                    list[new_idx..=og_idx].rotate_right(1);
+                    for index in &mut indexes {
+                        if *index == og_idx {
+                            *index = new_idx;
+                        } else if new_idx <= *index && *index < og_idx {
+                            *index += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn grove_coordinates(list: &[isize]) -> eyre::Result<isize> {
+    let idx_of_zero = list
+        .iter()
+        .position(|num| num == &0)
+        .ok_or_else(|| eyre::eyre!("can't find the index of 0 (list = {list:?})"))?;
+    Ok(list[(idx_of_zero + 1000) % list.len()]
+        + list[(idx_of_zero + 2000) % list.len()]
+        + list[(idx_of_zero + 3000) % list.len()])
+}
+
+/// Applies the decryption key, mixes the list and finds the grove coordinates
+fn decrypt(input: &str, key: isize, rounds: usize) -> eyre::Result<isize> {
+    let mut list = input
+        .lines()
+        .map(|line| {
+            let num = line.trim().parse::<isize>()?;
+            num.checked_mul(key)
+                .ok_or_else(|| eyre::eyre!("{num} times the decryption key {key} overflows"))
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    mix(&mut list, rounds)?;
+    grove_coordinates(&list)
+}
+
+/// Generates an encrypted file `size` numbers long, with exactly one zero
+pub struct Generator;
+
+impl InputGenerator for Generator {
+    fn generate(&self, rng: &mut StdRng, size: usize) -> String {
+        // mixing needs at least two numbers to move anything around
+        let size = size.max(2);
+
+        let mut numbers = (1..size)
+            .map(|_| {
+                let magnitude = rng.gen_range(1..=10_000_i64);
+                if rng.gen() {
+                    magnitude
+                } else {
+                    -magnitude
+                }
+            })
+            .collect::<Vec<_>>();
+        numbers.insert(rng.gen_range(0..size), 0);
+
+        numbers.into_iter().join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn rejects_keys_that_overflow() {
+        let error = decrypt("1\n0\n-3", isize::MAX, 1).unwrap_err();
+        assert!(error.to_string().contains("overflows"), "{error}");
+        assert!(decrypt("1\n0\n-3", isize::MAX / 3, 1).is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn mixing_keeps_the_same_numbers(
+            list in prop::collection::vec(-1000_isize..1000, 2..50),
+            rounds in 1_usize..4,
+        ) {
+            let mut mixed = list.clone();
+            mix(&mut mixed, rounds).unwrap();
+            prop_assert_eq!(mixed.into_iter().sorted().collect::<Vec<_>>(), list.into_iter().sorted().collect::<Vec<_>>());
+        }
+    }
+}
@@ -2,16 +2,17 @@ use std::{collections::HashMap, hint::unreachable_unchecked};
 
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take},
+    bytes::complete::take,
     character::complete::{digit1, one_of},
     sequence::{delimited, terminated, tuple},
-    IResult,
 };
 
+use crate::util::parse::{self, text};
+
 pub mod p1;
 pub mod p2;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum Monkey {
     Num(i64),
     Operation {
@@ -21,7 +22,7 @@ enum Monkey {
     },
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Operator {
     Add,
     Subtract,
@@ -29,8 +30,8 @@ enum Operator {
     Divide,
 }
 
-fn parse_monkey(input: &str) -> IResult<&str, (String, Monkey)> {
-    let (rem, name) = terminated(take(4_usize), tag(": "))(input)?;
+fn parse_monkey(input: &str) -> parse::Result<'_, (String, Monkey)> {
+    let (rem, name) = terminated(take(4_usize), text(": "))(input)?;
     let name = name.to_string();
 
     let (rem, monkey) = alt((digit1, take(11_usize)))(rem)?;
@@ -39,7 +40,7 @@ fn parse_monkey(input: &str) -> IResult<&str, (String, Monkey)> {
         Err(_) => {
             let (_, (left, op, right)) = tuple((
                 take(4_usize),
-                delimited(tag(" "), one_of("+-*/"), tag(" ")),
+                delimited(text(" "), one_of("+-*/"), text(" ")),
                 take(4_usize),
             ))(monkey)?;
 
@@ -106,3 +107,50 @@ fn calculate(
 
     calculated[monkey.as_ref()]
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn monkey() -> impl Strategy<Value = Monkey> {
+        let operator = prop::sample::select(vec![
+            Operator::Add,
+            Operator::Subtract,
+            Operator::Multipy,
+            Operator::Divide,
+        ]);
+        prop_oneof![
+            any::<u32>().prop_map(|num| Monkey::Num(num.into())),
+            ("[a-z]{4}", operator, "[a-z]{4}").prop_map(|(left, op, right)| Monkey::Operation {
+                left,
+                op,
+                right
+            }),
+        ]
+    }
+
+    fn render(name: &str, monkey: &Monkey) -> String {
+        match monkey {
+            Monkey::Num(num) => format!("{name}: {num}"),
+            Monkey::Operation { left, op, right } => {
+                let op = match op {
+                    Operator::Add => '+',
+                    Operator::Subtract => '-',
+                    Operator::Multipy => '*',
+                    Operator::Divide => '/',
+                };
+                format!("{name}: {left} {op} {right}")
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn parses_rendered_monkeys(name in "[a-z]{4}", monkey in monkey()) {
+            let rendered = render(&name, &monkey);
+            prop_assert_eq!(parse_monkey(&rendered), Ok(("", (name, monkey))));
+        }
+    }
+}
@@ -1,18 +1,13 @@
 use std::collections::HashMap;
 
 use super::{calculate, parse_monkey, Monkey, Operator};
+use crate::util::parse;
 
 pub fn solve(input: &str) -> eyre::Result<i64> {
-    let monkeys = input
-        .lines()
-        .filter_map(|line| {
-            let (_, (name, monkey)) = match parse_monkey(line.trim()) {
-                Ok(res) => res,
-                Err(err) => return Some(Err(eyre::eyre!("can't parse monkey: {err}"))),
-            };
-            (name != "humn").then(|| Ok((name, monkey)))
-        })
-        .collect::<eyre::Result<HashMap<_, _>>>()?;
+    let monkeys: HashMap<_, _> = parse::lines(input, parse_monkey)?
+        .into_iter()
+        .filter(|(name, _)| name != "humn")
+        .collect();
 
     let mut reversed = HashMap::new();
     for (name, monkey) in &monkeys {
@@ -1,13 +1,16 @@
-use std::{array, ops::RangeInclusive, str::Lines};
+use std::{collections::HashMap, ops::RangeInclusive, str::Lines};
 
+use itertools::Itertools;
 use nom::{branch::alt, bytes::complete::take, character::complete::digit1};
 
+use crate::{
+    util::{direction::Direction, grid::Grid},
+    visualize::Visualize,
+};
+
 pub mod p1;
 pub mod p2;
 
-const MAP_WIDTH: usize = 150;
-const MAP_HEIGHT: usize = 200;
-
 #[derive(Debug, Clone, Copy)]
 #[repr(u8)]
 enum Space {
@@ -16,69 +19,69 @@ enum Space {
     Inaccessible,
 }
 
-type Map = [[Space; MAP_WIDTH]; MAP_HEIGHT];
-
 #[derive(Debug, Clone)]
 struct ProcessedMap {
-    map: Map,
-    x_ranges: [RangeInclusive<usize>; MAP_HEIGHT],
-    y_ranges: [RangeInclusive<usize>; MAP_WIDTH],
+    map: Grid<Space>,
+    /// The columns that can be walked on in each row
+    x_ranges: Vec<RangeInclusive<usize>>,
+    /// The rows that can be walked on in each column
+    y_ranges: Vec<RangeInclusive<usize>>,
 }
 
-fn parse_map(line_iter: &mut Lines) -> Box<ProcessedMap> {
-    let map = array::from_fn(|_| {
-        let line = line_iter.next().unwrap().as_bytes();
-        array::from_fn(|x| match line.get(x) {
-            Some(b'.') => Space::Open,
-            Some(b'#') => Space::Wall,
-            Some(b' ') | None => Space::Inaccessible,
-            Some(_) => unreachable!(),
+/// Finds the first and last tiles in a row or column that aren't
+/// inaccessible
+fn accessible_range<'a>(
+    spaces: impl Iterator<Item = &'a Space>,
+) -> eyre::Result<RangeInclusive<usize>> {
+    let (low, high) = spaces
+        .enumerate()
+        .filter(|(_, space)| !matches!(space, Space::Inaccessible))
+        .fold(None, |range, (i, _)| match range {
+            Some((low, _)) => Some((low, i)),
+            None => Some((i, i)),
         })
-    });
-
-    let mut x_ranges = [None; MAP_HEIGHT];
-    let mut y_ranges = [None; MAP_WIDTH];
-
-    for (y, row) in map.iter().enumerate() {
-        for (x, space) in row.iter().enumerate() {
-            match space {
-                Space::Open | Space::Wall => {
-                    x_ranges[y] = match x_ranges[y] {
-                        Some((low, _)) => Some((low, x)),
-                        None => Some((x, x)),
-                    };
-                    y_ranges[x] = match y_ranges[x] {
-                        Some((low, _)) => Some((low, y)),
-                        None => Some((y, y)),
-                    };
-                }
-                Space::Inaccessible => {}
-            }
-        }
-    }
+        .ok_or_else(|| eyre::eyre!("there's a row or column with nowhere to walk"))?;
+    Ok(low..=high)
+}
+
+fn parse_map(line_iter: &mut Lines) -> eyre::Result<ProcessedMap> {
+    let map = line_iter.take_while(|line| !line.is_empty()).join("\n");
+    let map = Grid::parse_ragged(&map, Space::Inaccessible, |space| match space {
+        '.' => Some(Space::Open),
+        '#' => Some(Space::Wall),
+        ' ' => Some(Space::Inaccessible),
+        _ => None,
+    })?;
 
-    let x_ranges = {
-        let mut iter = x_ranges.into_iter().map(|x_range| {
-            let (low, high) = x_range.unwrap();
-            low..=high
-        });
-        std::array::from_fn(|_| iter.next().unwrap())
-    };
-    let y_ranges = {
-        let mut iter = y_ranges.into_iter().map(|y_range| {
-            let (low, high) = y_range.unwrap();
-            low..=high
-        });
-        std::array::from_fn(|_| iter.next().unwrap())
-    };
-
-    Box::new(ProcessedMap {
+    let x_ranges = map
+        .rows()
+        .map(|row| accessible_range(row.iter()))
+        .collect::<eyre::Result<_>>()?;
+    let y_ranges = map
+        .columns()
+        .map(accessible_range)
+        .collect::<eyre::Result<_>>()?;
+
+    Ok(ProcessedMap {
         map,
         x_ranges,
         y_ranges,
     })
 }
 
+impl ProcessedMap {
+    /// The leftmost open tile of the top row, where the path starts
+    fn start(&self) -> eyre::Result<(usize, usize)> {
+        let x = self
+            .map
+            .row(0)
+            .iter()
+            .position(|space| matches!(space, Space::Open))
+            .ok_or_else(|| eyre::eyre!("there's nowhere to start in the top row"))?;
+        Ok((x, 0))
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum Instruction {
     Move(usize),
@@ -102,3 +105,130 @@ fn parse_instructions<'a>(input: &'a str) -> impl Iterator<Item = Instruction> +
         })
     })
 }
+
+/// Moves `steps` tiles from `(x, y)` in the direction it's facing, returning
+/// the new position and facing
+type Mover = fn(&ProcessedMap, usize, usize, Direction, usize) -> (usize, usize, Direction);
+
+/// The final password, from where the path ends and which way it ends up
+/// facing
+fn password(x: usize, y: usize, facing: Direction) -> eyre::Result<usize> {
+    let facing = facing
+        .facing_score()
+        .ok_or_else(|| eyre::eyre!("the path ended facing diagonally"))?;
+    Ok(1000 * (y + 1) + 4 * (x + 1) + facing)
+}
+
+/// Follows the path one tile at a time so that the walk can be watched
+pub struct Walk<'a> {
+    map: ProcessedMap,
+    instructions: Box<dyn Iterator<Item = Instruction> + 'a>,
+    move_by: Mover,
+
+    x: usize,
+    y: usize,
+    facing: Direction,
+    steps_remaining: usize,
+
+    trail: HashMap<(usize, usize), Direction>,
+}
+
+impl<'a> Walk<'a> {
+    fn new(input: &'a str, move_by: Mover) -> eyre::Result<Self> {
+        let mut line_iter = input.lines();
+        let map = parse_map(&mut line_iter)?;
+        let (x, y) = map.start()?;
+
+        let instructions = parse_instructions(
+            line_iter
+                .next()
+                .ok_or_else(|| eyre::eyre!("can't get instruction line"))?
+                .trim(),
+        );
+
+        Ok(Self {
+            map,
+            instructions: Box::new(instructions),
+            move_by,
+
+            x,
+            y,
+            facing: Direction::East,
+            steps_remaining: 0,
+
+            trail: HashMap::from([((x, y), Direction::East)]),
+        })
+    }
+}
+
+impl Visualize for Walk<'_> {
+    fn frame(&self) -> String {
+        const VIEW_WIDTH: usize = 80;
+        const VIEW_HEIGHT: usize = 40;
+
+        let map = &self.map.map;
+        let view_width = VIEW_WIDTH.min(map.width());
+        let view_height = VIEW_HEIGHT.min(map.height());
+
+        // keep the current position roughly in the middle of the view
+        let left = self
+            .x
+            .saturating_sub(view_width / 2)
+            .min(map.width() - view_width);
+        let top = self
+            .y
+            .saturating_sub(view_height / 2)
+            .min(map.height() - view_height);
+
+        let mut frame = format!(
+            "row {}, column {}, facing {}\n",
+            self.y + 1,
+            self.x + 1,
+            self.facing.arrow()
+        );
+        for y in top..(top + view_height) {
+            for x in left..(left + view_width) {
+                frame.push(match self.trail.get(&(x, y)) {
+                    Some(facing) => facing.arrow(),
+                    None => match map[(x, y)] {
+                        Space::Open => '.',
+                        Space::Wall => '#',
+                        Space::Inaccessible => ' ',
+                    },
+                });
+            }
+            frame.push('\n');
+        }
+        frame
+    }
+
+    fn step(&mut self) -> bool {
+        while self.steps_remaining == 0 {
+            match self.instructions.next() {
+                Some(Instruction::Move(steps)) => self.steps_remaining = steps,
+                Some(Instruction::TurnLeft) => {
+                    self.facing = self.facing.turn_left();
+                    self.trail.insert((self.x, self.y), self.facing);
+                    return true;
+                }
+                Some(Instruction::TurnRight) => {
+                    self.facing = self.facing.turn_right();
+                    self.trail.insert((self.x, self.y), self.facing);
+                    return true;
+                }
+                None => return false,
+            }
+        }
+
+        let (x, y, facing) = (self.move_by)(&self.map, self.x, self.y, self.facing, 1);
+        if (x, y) == (self.x, self.y) {
+            // we hit a wall, so the rest of the steps won't go anywhere
+            self.steps_remaining = 0;
+        } else {
+            self.steps_remaining -= 1;
+            (self.x, self.y, self.facing) = (x, y, facing);
+            self.trail.insert((x, y), facing);
+        }
+        true
+    }
+}
@@ -1,4 +1,5 @@
-use super::{parse_instructions, parse_map, Instruction, ProcessedMap, Space};
+use super::{parse_instructions, parse_map, password, Instruction, ProcessedMap, Space, Walk};
+use crate::util::direction::Direction;
 
 fn move_left_by(map: &ProcessedMap, mut x: usize, y: usize, steps: usize) -> (usize, usize) {
     for _ in 0..steps {
@@ -15,7 +16,7 @@ fn move_left_by(map: &ProcessedMap, mut x: usize, y: usize, steps: usize) -> (us
             None => *x_range.end(),
         };
 
-        if let Space::Open = map.map[y][new_x] {
+        if let Space::Open = map.map[(new_x, y)] {
             x = new_x;
         } else {
             break;
@@ -33,7 +34,7 @@ fn move_right_by(map: &ProcessedMap, mut x: usize, y: usize, steps: usize) -> (u
             new_x = *x_range.start()
         }
 
-        if let Space::Open = map.map[y][new_x] {
+        if let Space::Open = map.map[(new_x, y)] {
             x = new_x;
         } else {
             break;
@@ -58,7 +59,7 @@ fn move_up_by(map: &ProcessedMap, x: usize, mut y: usize, steps: usize) -> (usiz
             None => *y_range.end(),
         };
 
-        if let Space::Open = map.map[new_y][x] {
+        if let Space::Open = map.map[(x, new_y)] {
             y = new_y;
         } else {
             break;
@@ -76,7 +77,7 @@ fn move_down_by(map: &ProcessedMap, x: usize, mut y: usize, steps: usize) -> (us
             new_y = *y_range.start()
         }
 
-        if let Space::Open = map.map[new_y][x] {
+        if let Space::Open = map.map[(x, new_y)] {
             y = new_y;
         } else {
             break;
@@ -86,15 +87,29 @@ fn move_down_by(map: &ProcessedMap, x: usize, mut y: usize, steps: usize) -> (us
     (x, y)
 }
 
+fn move_by(
+    map: &ProcessedMap,
+    x: usize,
+    y: usize,
+    facing: Direction,
+    steps: usize,
+) -> (usize, usize, Direction) {
+    let (x, y) = match facing {
+        Direction::East => move_right_by(map, x, y, steps),
+        Direction::South => move_down_by(map, x, y, steps),
+        Direction::West => move_left_by(map, x, y, steps),
+        Direction::North => move_up_by(map, x, y, steps),
+        _ => unreachable!(),
+    };
+    (x, y, facing)
+}
+
 pub fn solve(input: &str) -> eyre::Result<usize> {
     let mut line_iter = input.lines();
-    let map = parse_map(&mut line_iter);
-
-    let _ = line_iter.next();
+    let map = parse_map(&mut line_iter)?;
 
-    let mut x = 50;
-    let mut y = 0;
-    let mut orientation = 0_usize;
+    let (mut x, mut y) = map.start()?;
+    let mut facing = Direction::East;
     for instruction in parse_instructions(
         line_iter
             .next()
@@ -103,18 +118,16 @@ pub fn solve(input: &str) -> eyre::Result<usize> {
     ) {
         match instruction {
             Instruction::Move(steps) => {
-                (x, y) = match orientation {
-                    0 => move_right_by(&map, x, y, steps),
-                    1 => move_down_by(&map, x, y, steps),
-                    2 => move_left_by(&map, x, y, steps),
-                    3 => move_up_by(&map, x, y, steps),
-                    _ => unreachable!(),
-                }
+                (x, y, facing) = move_by(&map, x, y, facing, steps);
             }
-            Instruction::TurnLeft => orientation = orientation.checked_sub(1).unwrap_or(3),
-            Instruction::TurnRight => orientation = (orientation + 1) % 4,
+            Instruction::TurnLeft => facing = facing.turn_left(),
+            Instruction::TurnRight => facing = facing.turn_right(),
         }
     }
 
-    Ok(1000 * (y + 1) + 4 * (x + 1) + orientation)
+    password(x, y, facing)
+}
+
+pub fn visualize(input: &str) -> eyre::Result<Walk<'_>> {
+    Walk::new(input, move_by)
 }
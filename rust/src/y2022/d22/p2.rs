@@ -1,72 +1,70 @@
-use super::{parse_instructions, parse_map, Instruction, ProcessedMap, Space};
+use super::{parse_instructions, parse_map, password, Instruction, ProcessedMap, Space, Walk};
+use crate::util::direction::Direction::{self, *};
 
 fn move_by(
     map: &ProcessedMap,
     mut x: usize,
     mut y: usize,
-    mut orientation: usize,
+    mut facing: Direction,
     steps: usize,
-) -> (usize, usize, usize) {
+) -> (usize, usize, Direction) {
     for _ in 0..steps {
-        let (new_x, new_y, new_orientation) = match (x, y, orientation) {
+        let (new_x, new_y, new_facing) = match (x, y, facing) {
             // to the left
-            (50, 0..=49, 2) => (0, 149 - y, 0),
-            (51..=149, 0..=49, 2) => (x - 1, y, 2),
-            (50, 50..=99, 2) => (y - 50, 100, 1),
-            (51..=99, 50..=99, 2) => (x - 1, y, 2),
-            (0, 100..=149, 2) => (50, 149 - y, 0),
-            (1..=99, 100..=149, 2) => (x - 1, y, 2),
-            (0, 150..=199, 2) => (y - 100, 0, 1),
-            (1..=49, 150..=199, 2) => (x - 1, y, 2),
+            (50, 0..=49, West) => (0, 149 - y, East),
+            (51..=149, 0..=49, West) => (x - 1, y, West),
+            (50, 50..=99, West) => (y - 50, 100, South),
+            (51..=99, 50..=99, West) => (x - 1, y, West),
+            (0, 100..=149, West) => (50, 149 - y, East),
+            (1..=99, 100..=149, West) => (x - 1, y, West),
+            (0, 150..=199, West) => (y - 100, 0, South),
+            (1..=49, 150..=199, West) => (x - 1, y, West),
 
             // to the right
-            (50..=148, 0..=49, 0) => (x + 1, y, 0),
-            (149, 0..=49, 0) => (99, 149 - y, 2),
-            (50..=98, 50..=99, 0) => (x + 1, y, 0),
-            (99, 50..=99, 0) => (y + 50, 49, 3),
-            (0..=98, 100..=149, 0) => (x + 1, y, 0),
-            (99, 100..=149, 0) => (149, 149 - y, 2),
-            (0..=48, 150..=199, 0) => (x + 1, y, 0),
-            (49, 150..=199, 0) => (y - 100, 149, 3),
+            (50..=148, 0..=49, East) => (x + 1, y, East),
+            (149, 0..=49, East) => (99, 149 - y, West),
+            (50..=98, 50..=99, East) => (x + 1, y, East),
+            (99, 50..=99, East) => (y + 50, 49, North),
+            (0..=98, 100..=149, East) => (x + 1, y, East),
+            (99, 100..=149, East) => (149, 149 - y, West),
+            (0..=48, 150..=199, East) => (x + 1, y, East),
+            (49, 150..=199, East) => (y - 100, 149, North),
 
             // up
-            (0..=49, 100, 3) => (50, x + 50, 0),
-            (0..=49, 101..=199, 3) => (x, y - 1, 3),
-            (50..=99, 0, 3) => (0, x + 100, 0),
-            (50..=99, 1..=149, 3) => (x, y - 1, 3),
-            (100..=149, 0, 3) => (x - 100, 199, 3),
-            (100..=149, 1..=49, 3) => (x, y - 1, 3),
+            (0..=49, 100, North) => (50, x + 50, East),
+            (0..=49, 101..=199, North) => (x, y - 1, North),
+            (50..=99, 0, North) => (0, x + 100, East),
+            (50..=99, 1..=149, North) => (x, y - 1, North),
+            (100..=149, 0, North) => (x - 100, 199, North),
+            (100..=149, 1..=49, North) => (x, y - 1, North),
 
             // down
-            (0..=49, 100..=198, 1) => (x, y + 1, 1),
-            (0..=49, 199, 1) => (x + 100, 0, 1),
-            (50..=99, 0..=148, 1) => (x, y + 1, 1),
-            (50..=99, 149, 1) => (49, x + 100, 2),
-            (100..=149, 0..=48, 1) => (x, y + 1, 1),
-            (100..=149, 49, 1) => (99, x - 50, 2),
+            (0..=49, 100..=198, South) => (x, y + 1, South),
+            (0..=49, 199, South) => (x + 100, 0, South),
+            (50..=99, 0..=148, South) => (x, y + 1, South),
+            (50..=99, 149, South) => (49, x + 100, West),
+            (100..=149, 0..=48, South) => (x, y + 1, South),
+            (100..=149, 49, South) => (99, x - 50, West),
 
             _ => unreachable!(),
         };
 
-        if let Space::Open = map.map[new_y][new_x] {
-            (x, y, orientation) = (new_x, new_y, new_orientation);
+        if let Space::Open = map.map[(new_x, new_y)] {
+            (x, y, facing) = (new_x, new_y, new_facing);
         } else {
             break;
         }
     }
 
-    (x, y, orientation)
+    (x, y, facing)
 }
 
 pub fn solve(input: &str) -> eyre::Result<usize> {
     let mut line_iter = input.lines();
-    let map = parse_map(&mut line_iter);
+    let map = parse_map(&mut line_iter)?;
 
-    let _ = line_iter.next();
-
-    let mut x = 50;
-    let mut y = 0;
-    let mut orientation = 0_usize;
+    let (mut x, mut y) = map.start()?;
+    let mut facing = East;
     for instruction in parse_instructions(
         line_iter
             .next()
@@ -75,12 +73,16 @@ pub fn solve(input: &str) -> eyre::Result<usize> {
     ) {
         match instruction {
             Instruction::Move(steps) => {
-                (x, y, orientation) = move_by(&map, x, y, orientation, steps);
+                (x, y, facing) = move_by(&map, x, y, facing, steps);
             }
-            Instruction::TurnLeft => orientation = orientation.checked_sub(1).unwrap_or(3),
-            Instruction::TurnRight => orientation = (orientation + 1) % 4,
+            Instruction::TurnLeft => facing = facing.turn_left(),
+            Instruction::TurnRight => facing = facing.turn_right(),
         }
     }
 
-    Ok(1000 * (y + 1) + 4 * (x + 1) + orientation)
+    password(x, y, facing)
+}
+
+pub fn visualize(input: &str) -> eyre::Result<Walk<'_>> {
+    Walk::new(input, move_by)
 }
@@ -1,18 +1,18 @@
-use std::collections::{HashMap, HashSet};
-
-use eyre::Context;
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng};
 use to_method::To;
 
+use crate::{
+    generate::InputGenerator,
+    util::{bitgrid::BitGrid, direction::Direction, grid::Grid},
+    visualize::Visualize,
+};
+
 pub mod p1;
 pub mod p2;
 
-enum Direction {
-    North,
-    South,
-    West,
-    East,
-}
-
+/// The order the elves consider moving in, starting from a different one each
+/// round
 const DIRECTIONS: [Direction; 4] = [
     Direction::North,
     Direction::South,
@@ -20,176 +20,161 @@ const DIRECTIONS: [Direction; 4] = [
     Direction::East,
 ];
 
+/// How much room to leave around the elves whenever they get to the edge of
+/// the map, so that it doesn't have to grow every round
+const PADDING: usize = 8;
+
 #[derive(Debug, Clone)]
 struct State {
-    map: HashSet<(i32, i32)>,
-    min_x: i32,
-    max_x: i32,
-    min_y: i32,
-    max_y: i32,
+    /// Which tiles have an elf on them, with empty ground all around the edge
+    map: BitGrid,
     directions_index: usize,
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Action {
-    Move(i32, i32),
-    DoNothing,
-}
-
 impl State {
     fn from_input(input: &str) -> eyre::Result<Self> {
-        let map = input
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.trim()
-                    .as_bytes()
-                    .iter()
-                    .enumerate()
-                    .filter_map(move |(x, &spot)| {
-                        let x = match x.try_to::<i32>().wrap_err("can't convert x to an i32") {
-                            Ok(x) => x,
-                            Err(err) => return Some(Err(err)),
-                        };
-                        let y = match y.try_to::<i32>().wrap_err("can't convert y to an i32") {
-                            Ok(y) => y,
-                            Err(err) => return Some(Err(err)),
-                        };
-                        (spot == b'#').then_some(Ok((x, y)))
-                    })
-            })
-            .flatten()
-            .collect::<eyre::Result<HashSet<(i32, i32)>>>()?;
-
-        let (min_x, max_x, min_y, max_y) = map.iter().fold(
-            (i32::MAX, i32::MIN, i32::MAX, i32::MIN),
-            |(min_x, max_x, min_y, max_y), &(x, y)| {
-                (min_x.min(x), max_x.max(x), min_y.min(y), max_y.max(y))
-            },
-        );
+        let grove = Grid::parse(input, |spot| match spot {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        let mut map = BitGrid::new(grove.width(), grove.height());
+        for ((x, y), &elf) in grove.iter() {
+            map.set(x, y, elf);
+        }
 
         Ok(Self {
-            map,
-            min_x,
-            max_x,
-            min_y,
-            max_y,
+            map: map.padded(PADDING),
             directions_index: 0,
         })
     }
 
-    fn elf_proposals(&mut self) -> (Vec<(i32, i32, Action)>, HashMap<(i32, i32), u32>, bool) {
-        let mut proposed_actions = Vec::new();
-        let mut proposed_destinations = HashMap::new();
-        let mut elf_moved = false;
-        'outer: for &(x, y) in &self.map {
-            let no_elves_around = itertools::iproduct!(-1..=1, -1..=1).all(|(dx, dy)| {
-                if dx == 0 && dy == 0 {
-                    true
-                } else {
-                    !self.map.contains(&(x + dx, y + dy))
-                }
-            });
-            if no_elves_around {
-                proposed_actions.push((x, y, Action::DoNothing));
-                continue 'outer;
-            }
-            elf_moved = true;
-
-            for directions_index in (self.directions_index..4).chain(0..self.directions_index) {
-                match DIRECTIONS[directions_index] {
-                    Direction::North => {
-                        let can_move_north =
-                            (-1..=1).all(|dx| !self.map.contains(&(x + dx, y - 1)));
-                        if can_move_north {
-                            proposed_actions.push((x, y, Action::Move(x, y - 1)));
-                            *proposed_destinations.entry((x, y - 1)).or_insert(0) += 1;
-                            continue 'outer;
-                        }
-                    }
-                    Direction::South => {
-                        let can_move_south =
-                            (-1..=1).all(|dx| !self.map.contains(&(x + dx, y + 1)));
-                        if can_move_south {
-                            proposed_actions.push((x, y, Action::Move(x, y + 1)));
-                            *proposed_destinations.entry((x, y + 1)).or_insert(0) += 1;
-                            continue 'outer;
-                        }
-                    }
-                    Direction::West => {
-                        let can_move_west = (-1..=1).all(|dy| !self.map.contains(&(x - 1, y + dy)));
-                        if can_move_west {
-                            proposed_actions.push((x, y, Action::Move(x - 1, y)));
-                            *proposed_destinations.entry((x - 1, y)).or_insert(0) += 1;
-                            continue 'outer;
-                        }
-                    }
-                    Direction::East => {
-                        let can_move_east = (-1..=1).all(|dy| !self.map.contains(&(x + 1, y + dy)));
-                        if can_move_east {
-                            proposed_actions.push((x, y, Action::Move(x + 1, y)));
-                            *proposed_destinations.entry((x + 1, y)).or_insert(0) += 1;
-                            continue 'outer;
-                        }
-                    }
-                }
-            }
-
-            proposed_actions.push((x, y, Action::DoNothing));
+    /// Makes sure there's an empty row or column on every side of the elves,
+    /// so that none of them fall off the edge when the whole map is shifted
+    fn make_room(&mut self) {
+        let (width, height) = (self.map.width(), self.map.height());
+        let at_edge = !self.map.row(0).is_empty()
+            || !self.map.row(height - 1).is_empty()
+            || self.map.rows().any(|row| row.get(0) || row.get(width - 1));
+        if at_edge {
+            self.map = self.map.padded(PADDING);
         }
+    }
 
-        (proposed_actions, proposed_destinations, elf_moved)
+    /// The tiles that have an elf on any of the three tiles in front of them
+    /// going `direction`
+    fn blocked(&self, direction: Direction) -> BitGrid {
+        let step = direction.delta::<isize>();
+        let side = direction.turn_right().delta::<isize>();
+        let ahead = self.map.shifted(-step.x, -step.y);
+        let beside = &ahead.shifted(side.x, side.y) | &ahead.shifted(-side.x, -side.y);
+        &ahead | &beside
     }
 
     /// Returns whether any elves moved this round
     fn simulate_round(&mut self) -> bool {
-        let (proposed_actions, proposed_destinations, elf_moved) = self.elf_proposals();
-        if !elf_moved {
+        self.make_room();
+        let crowded = &self.map & &self.map.surrounded();
+        if crowded.is_empty() {
             return false;
         }
 
-        let mut new_min_x = i32::MAX;
-        let mut new_max_x = i32::MIN;
-        let mut new_min_y = i32::MAX;
-        let mut new_max_y = i32::MIN;
-        for (x, y, action) in proposed_actions {
-            let (new_x, new_y) = match action {
-                Action::Move(new_x, new_y) => {
-                    if proposed_destinations[&(new_x, new_y)] == 1 {
-                        self.map.remove(&(x, y));
-                        self.map.insert((new_x, new_y));
-                        (new_x, new_y)
-                    } else {
-                        (x, y)
-                    }
-                }
-                Action::DoNothing => (x, y),
-            };
-            new_min_x = new_min_x.min(new_x);
-            new_max_x = new_max_x.max(new_x);
-            new_min_y = new_min_y.min(new_y);
-            new_max_y = new_max_y.max(new_y);
+        // each elf proposes the first way it can go, so whoever's still
+        // undecided after a direction is the ones that are blocked that way
+        let mut undecided = crowded;
+        let mut proposals = Vec::with_capacity(DIRECTIONS.len());
+        for directions_index in (self.directions_index..4).chain(0..self.directions_index) {
+            let direction = DIRECTIONS[directions_index];
+            let blocked = self.blocked(direction);
+            let step = direction.delta::<isize>();
+            let proposed = (&undecided & &!&blocked).shifted(step.x, step.y);
+            undecided = &undecided & &blocked;
+            proposals.push((direction, proposed));
         }
 
-        self.min_x = new_min_x;
-        self.max_x = new_max_x;
-        self.min_y = new_min_y;
-        self.max_y = new_max_y;
+        // an elf stepping into a tile needs the three next to it on its side
+        // to be empty, so the only other elf that can propose the same tile is
+        // one coming from the opposite side
+        let mut moved = BitGrid::new(self.map.width(), self.map.height());
+        let mut left = moved.clone();
+        for (direction, proposed) in &proposals {
+            let (_, opposite) = proposals
+                .iter()
+                .find(|(other, _)| *other == direction.turn_around())
+                .expect("every direction gets a proposal");
+            let unopposed = proposed & &!opposite;
+            let step = direction.delta::<isize>();
+            left = &left | &unopposed.shifted(-step.x, -step.y);
+            moved = &moved | &unopposed;
+        }
+        self.map = &(&self.map & &!&left) | &moved;
         self.directions_index = (self.directions_index + 1) % 4;
 
         true
     }
 
-    fn empty_ground_tiles(&self) -> u32 {
-        let mut num_tiles = 0;
-        for x in self.min_x..=self.max_x {
-            for y in self.min_y..=self.max_y {
-                if !self.map.contains(&(x, y)) {
-                    num_tiles += 1;
-                }
-            }
+    /// The corners of the smallest rectangle containing every elf
+    fn bounds(&self) -> Option<((usize, usize), (usize, usize))> {
+        let occupied = |&y: &usize| !self.map.row(y).is_empty();
+        let min_y = (0..self.map.height()).find(occupied)?;
+        let max_y = (0..self.map.height()).rfind(occupied)?;
+        let min_x = self.map.rows().filter_map(|row| row.ones().next()).min()?;
+        let max_x = self.map.rows().filter_map(|row| row.ones().last()).max()?;
+        Some(((min_x, min_y), (max_x, max_y)))
+    }
+
+    fn empty_ground_tiles(&self) -> eyre::Result<u32> {
+        let Some(((min_x, min_y), (max_x, max_y))) = self.bounds() else {
+            return Ok(0);
+        };
+
+        let area = (max_x - min_x + 1) * (max_y - min_y + 1);
+        Ok((area - self.map.count_ones()).try_to()?)
+    }
+}
+
+/// Runs the elves' rounds one at a time so that they can be watched spreading out
+pub struct Simulation {
+    state: State,
+    rounds_remaining: Option<usize>,
+}
+
+impl Visualize for Simulation {
+    fn frame(&self) -> String {
+        let state = &self.state;
+        let Some(((min_x, min_y), (max_x, max_y))) = state.bounds() else {
+            return String::new();
+        };
+        (min_y..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| if state.map.get(x, y) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+
+    fn step(&mut self) -> bool {
+        match &mut self.rounds_remaining {
+            Some(0) => return false,
+            Some(rounds_remaining) => *rounds_remaining -= 1,
+            None => {}
         }
+        self.state.simulate_round()
+    }
+}
+
+/// Generates a `size` by `size` grove where about half the tiles have elves
+pub struct Generator;
 
-        num_tiles
+impl InputGenerator for Generator {
+    fn generate(&self, rng: &mut StdRng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.gen() { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .join("\n")
     }
 }
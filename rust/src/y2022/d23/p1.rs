@@ -1,11 +1,27 @@
-use super::State;
+use super::{Simulation, State};
+use crate::params::{Param, Params};
 
-pub fn solve(input: &str) -> eyre::Result<u32> {
+const ROUNDS: Param = Param {
+    name: "rounds",
+    default: 10,
+    help: "how many rounds the elves spread out for",
+};
+
+pub const PARAMS: &[Param] = &[ROUNDS];
+
+pub fn solve(input: &str, params: &Params) -> eyre::Result<u32> {
     let mut state = State::from_input(input)?;
 
-    for _ in 0..10 {
+    for _ in 0..params.get(&ROUNDS)? {
         state.simulate_round();
     }
 
-    Ok(state.empty_ground_tiles())
+    state.empty_ground_tiles()
+}
+
+pub fn visualize(input: &str, params: &Params) -> eyre::Result<Simulation> {
+    Ok(Simulation {
+        state: State::from_input(input)?,
+        rounds_remaining: Some(params.get(&ROUNDS)?),
+    })
 }
@@ -1,4 +1,4 @@
-use super::State;
+use super::{Simulation, State};
 
 pub fn solve(input: &str) -> eyre::Result<u32> {
     let mut state = State::from_input(input)?;
@@ -10,3 +10,10 @@ pub fn solve(input: &str) -> eyre::Result<u32> {
 
     Ok(round)
 }
+
+pub fn visualize(input: &str) -> eyre::Result<Simulation> {
+    Ok(Simulation {
+        state: State::from_input(input)?,
+        rounds_remaining: None,
+    })
+}
@@ -1,130 +1,201 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    mem::MaybeUninit,
-    str::Lines,
-};
+use std::{collections::HashSet, str::Lines};
 
 use to_method::To;
 
+use crate::{
+    util::{bitgrid::BitGrid, cycle, direction::Direction, math, search},
+    visualize::Visualize,
+};
+
 pub mod p1;
 pub mod p2;
 
-const MAP_WIDTH: u64 = 150;
-const MAP_HEIGHT: u64 = 20;
-const NUM_BLIZZARD_STATES: usize = 300;
+/// Where a blizzard is and which way it's blowing
+type Blizzard = (u64, u64, Direction);
 
-type BlizzardState = [Row; MAP_HEIGHT as usize];
-type BlizzardStates = [BlizzardState; NUM_BLIZZARD_STATES];
+/// Which tiles have at least one blizzard in them
+type BlizzardState = BitGrid;
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-#[repr(u8)]
-enum BlizzardDir {
-    Left,
-    Right,
-    Up,
-    Down,
+/// The part of the valley between the walls, which the blizzards blow around
+/// in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Valley {
+    width: u64,
+    height: u64,
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Row([u64; 3]);
-
-impl Row {
-    fn from_blizzard_state(y: u64, state: &HashSet<(u64, u64, BlizzardDir)>) -> eyre::Result<Self> {
-        let mut row = [0; 3];
-
-        for x in 0..MAP_WIDTH {
-            if state.contains(&(x, y, BlizzardDir::Left))
-                || state.contains(&(x, y, BlizzardDir::Right))
-                || state.contains(&(x, y, BlizzardDir::Up))
-                || state.contains(&(x, y, BlizzardDir::Down))
-            {
-                let index = x / 64;
-                let to_shift = 63 - (x % 64);
-                let mask = 1 << to_shift;
-                row[index.try_to::<usize>()?] |= mask;
-            }
+impl Valley {
+    /// The blizzards wrap around the valley both ways, so they're all back
+    /// where they started once the horizontal and vertical ones are at the
+    /// same time, if not sooner
+    fn max_blizzard_states(self) -> eyre::Result<usize> {
+        let states = math::lcm(self.width, self.height)
+            .ok_or_else(|| eyre::eyre!("the blizzards take too long to repeat"))?;
+        Ok(states.try_to()?)
+    }
+
+    fn occupied_tiles(self, state: &HashSet<Blizzard>) -> eyre::Result<BlizzardState> {
+        let mut occupied = BitGrid::new(self.width.try_to()?, self.height.try_to()?);
+        for &(x, y, _) in state {
+            occupied.set(x.try_to()?, y.try_to()?, true);
+        }
+        Ok(occupied)
+    }
+
+    fn try_move_left(self, x: u64, y: u64) -> Option<u64> {
+        if y == u64::MAX || y == self.height {
+            None
+        } else {
+            (x > 0).then(|| x - 1)
+        }
+    }
+
+    fn try_move_right(self, x: u64, y: u64) -> Option<u64> {
+        if y == u64::MAX || y == self.height {
+            None
+        } else {
+            (x < self.width - 1).then(|| x + 1)
         }
+    }
+
+    fn try_move_up(self, x: u64, y: u64) -> Option<u64> {
+        if x == 0 && y == 0 {
+            Some(u64::MAX)
+        } else if y == u64::MAX {
+            None
+        } else {
+            (y > 0).then(|| y - 1)
+        }
+    }
 
-        Ok(Self(row))
+    fn try_move_down(self, x: u64, y: u64) -> Option<u64> {
+        if x == 0 && y == u64::MAX {
+            Some(0)
+        } else if x == self.width - 1 && y == self.height - 1 {
+            Some(self.height)
+        } else {
+            (y < self.height - 1).then(|| y + 1)
+        }
     }
 
-    fn blizzard_at(&self, x: u64) -> eyre::Result<bool> {
-        let index = (x / 64).try_to::<usize>()?;
-        let to_shift = 63 - (x % 64);
-        let mask = 1 << to_shift;
-        Ok(self.0[index] & mask != 0)
+    fn will_be_safe(
+        self,
+        x: u64,
+        y: u64,
+        new_blizzard_state_index: usize,
+        blizzard_states: &[BlizzardState],
+    ) -> eyre::Result<bool> {
+        if x == 0 && y == u64::MAX {
+            Ok(true)
+        } else if x == self.width - 1 && y == self.height {
+            Ok(true)
+        } else {
+            Ok(!blizzard_states[new_blizzard_state_index].get(x.try_to()?, y.try_to()?))
+        }
     }
 }
 
-fn parse_states(lines: &mut Lines) -> eyre::Result<Box<BlizzardStates>> {
-    // SAFETY: it's safe to assume the array is initialized since it contains
-    // `MaybeUninit`s
-    let mut map_states = Box::new(
-        [unsafe {
-            MaybeUninit::new([MaybeUninit::<Row>::uninit(); MAP_HEIGHT as usize]).assume_init()
-        }; NUM_BLIZZARD_STATES],
-    );
+/// Reads the rows of the valley up to and including its bottom wall, along
+/// with how big the valley is
+fn parse_blizzards(lines: &mut Lines) -> eyre::Result<(Valley, HashSet<Blizzard>)> {
+    let mut blizzards = HashSet::new();
+    let mut width = None;
+    let mut height = 0;
+    let mut found_bottom_wall = false;
+    for line in lines {
+        let row = line
+            .trim()
+            .strip_prefix('#')
+            .and_then(|row| row.strip_suffix('#'))
+            .ok_or_else(|| eyre::eyre!("row {} of the valley isn't between walls", height + 1))?;
+        // there's only ever wall between the walls in the bottom one
+        if row.contains('#') {
+            found_bottom_wall = true;
+            break;
+        }
 
-    let mut cur_state = lines
-        .enumerate()
-        .take(MAP_HEIGHT.try_to::<usize>()?)
-        .map(|(y, line)| {
-            let line = line.trim().as_bytes();
-            line[1..(line.len() - 1)]
-                .iter()
-                .enumerate()
-                .filter_map(move |(x, &spot)| {
-                    let direction = match spot {
-                        b'.' => return None,
-                        b'<' => BlizzardDir::Left,
-                        b'>' => BlizzardDir::Right,
-                        b'^' => BlizzardDir::Up,
-                        b'v' => BlizzardDir::Down,
-                        _ => unreachable!(),
-                    };
-                    let x = match x.try_to::<u64>() {
-                        Ok(x) => x,
-                        Err(err) => return Some(Err(err)),
-                    };
-                    let y = match y.try_to::<u64>() {
-                        Ok(y) => y,
-                        Err(err) => return Some(Err(err)),
-                    };
-                    Some(Ok((x, y, direction)))
-                })
-        })
-        .flatten()
-        .collect::<Result<HashSet<(u64, u64, BlizzardDir)>, _>>()?;
+        let row_width = row.chars().count();
+        if *width.get_or_insert(row_width) != row_width {
+            eyre::bail!(
+                "row {} of the valley isn't as wide as the others",
+                height + 1
+            );
+        }
+        for (x, spot) in row.chars().enumerate() {
+            if spot == '.' {
+                continue;
+            }
+            let direction = Direction::from_arrow(spot).ok_or_else(|| {
+                eyre::eyre!("there's a {spot:?} in row {} of the valley", height + 1)
+            })?;
+            blizzards.insert((x.try_to()?, height, direction));
+        }
+        height += 1;
+    }
 
-    for uninit_map in map_states.iter_mut() {
-        write_map(uninit_map, &cur_state)?;
-        simulate_state_step(&mut cur_state);
+    if !found_bottom_wall {
+        eyre::bail!("the valley doesn't have a bottom wall");
     }
+    let width = width.ok_or_else(|| eyre::eyre!("the valley doesn't have any rows"))?;
+    let valley = Valley {
+        width: width.try_to()?,
+        height,
+    };
+    Ok((valley, blizzards))
+}
 
-    let raw = Box::into_raw(map_states);
-    // SAFETY: we initialized everything
-    Ok(unsafe { Box::from_raw(raw.cast()) })
+/// Everything about where the blizzards are, in an order that doesn't depend
+/// on how they're stored
+fn fingerprint(state: &HashSet<Blizzard>) -> Vec<(u64, u64, u8)> {
+    let mut fingerprint: Vec<_> = state
+        .iter()
+        .map(|&(x, y, direction)| (x, y, direction as u8))
+        .collect();
+    fingerprint.sort_unstable();
+    fingerprint
 }
 
-fn write_map(
-    uninit_map: &mut [MaybeUninit<Row>; MAP_HEIGHT as usize],
-    state: &HashSet<(u64, u64, BlizzardDir)>,
-) -> eyre::Result<()> {
-    for y in 0..MAP_HEIGHT {
-        uninit_map[y.try_to::<usize>()?].write(Row::from_blizzard_state(y, state)?);
+/// Where the blizzards are in each minute until they start repeating
+fn parse_states(lines: &mut Lines) -> eyre::Result<(Valley, Vec<BlizzardState>)> {
+    let (valley, blizzards) = parse_blizzards(lines)?;
+    let history = cycle::detect(
+        blizzards,
+        valley.max_blizzard_states()?,
+        |state| simulate_state_step(valley, state),
+        fingerprint,
+        |state| valley.occupied_tiles(state),
+    );
+    let cycle = history
+        .cycle
+        .ok_or_else(|| eyre::eyre!("the blizzards didn't start repeating"))?;
+    // every blizzard state comes from exactly one before it, so the first
+    // state to come back round is the one they started in
+    if cycle.start != 0 {
+        eyre::bail!(
+            "the blizzards started repeating after {} minutes",
+            cycle.start
+        );
     }
 
-    Ok(())
+    let map_states = history
+        .into_metrics()
+        .into_iter()
+        .take(cycle.length)
+        .collect::<eyre::Result<_>>()?;
+    Ok((valley, map_states))
 }
 
-fn simulate_state_step(state: &mut HashSet<(u64, u64, BlizzardDir)>) {
+fn simulate_state_step(valley: Valley, state: &mut HashSet<Blizzard>) {
+    let Valley { width, height } = valley;
     let new_coords = state
         .drain()
-        .map(|(x, y, direction)| match direction {
-            BlizzardDir::Left => (x.checked_sub(1).unwrap_or(MAP_WIDTH - 1), y, direction),
-            BlizzardDir::Right => ((x + 1) % MAP_WIDTH, y, direction),
-            BlizzardDir::Up => (x, y.checked_sub(1).unwrap_or(MAP_HEIGHT - 1), direction),
-            BlizzardDir::Down => (x, (y + 1) % MAP_HEIGHT, direction),
+        .map(|(x, y, direction)| {
+            // blizzards that leave one side of the valley come back in on the
+            // other
+            let step = direction.delta::<i64>();
+            let x = (x + width).wrapping_add_signed(step.x) % width;
+            let y = (y + height).wrapping_add_signed(step.y) % height;
+            (x, y, direction)
         })
         .collect::<Vec<_>>();
     for new_coord in new_coords {
@@ -132,109 +203,211 @@ fn simulate_state_step(state: &mut HashSet<(u64, u64, BlizzardDir)>) {
     }
 }
 
-fn try_move_left(x: u64, y: u64) -> Option<u64> {
-    if y == u64::MAX || y == MAP_HEIGHT {
-        None
-    } else {
-        (x > 0).then(|| x - 1)
-    }
+/// Moves the blizzards one minute at a time
+pub struct Blizzards {
+    valley: Valley,
+    blizzards: HashSet<Blizzard>,
 }
 
-fn try_move_right(x: u64, y: u64) -> Option<u64> {
-    if y == u64::MAX || y == MAP_HEIGHT {
-        None
-    } else {
-        (x < MAP_WIDTH - 1).then(|| x + 1)
-    }
-}
+impl Visualize for Blizzards {
+    fn frame(&self) -> String {
+        let Valley { width, height } = self.valley;
+        let wall = std::iter::repeat_n('#', width as usize);
 
-fn try_move_up(x: u64, y: u64) -> Option<u64> {
-    if x == 0 && y == 0 {
-        Some(u64::MAX)
-    } else if y == u64::MAX {
-        None
-    } else {
-        (y > 0).then(|| y - 1)
+        let mut frame = String::from("#.");
+        frame.extend(wall.clone());
+        for y in 0..height {
+            frame.push_str("\n#");
+            for x in 0..width {
+                let blizzards = Direction::CARDINALS
+                    .into_iter()
+                    .filter(|&direction| self.blizzards.contains(&(x, y, direction)))
+                    .collect::<Vec<_>>();
+                frame.push(match blizzards[..] {
+                    [] => '.',
+                    [direction] => direction.arrow(),
+                    _ => char::from_digit(blizzards.len() as u32, 10).unwrap_or('*'),
+                });
+            }
+            frame.push('#');
+        }
+        frame.push('\n');
+        frame.extend(wall);
+        frame.push_str(".#");
+        frame
     }
-}
 
-fn try_move_down(x: u64, y: u64) -> Option<u64> {
-    if x == 0 && y == u64::MAX {
-        Some(0)
-    } else if x == MAP_WIDTH - 1 && y == MAP_HEIGHT - 1 {
-        Some(MAP_HEIGHT)
-    } else {
-        (y < MAP_HEIGHT - 1).then(|| y + 1)
+    fn step(&mut self) -> bool {
+        simulate_state_step(self.valley, &mut self.blizzards);
+        true
     }
 }
 
-fn will_be_safe(
-    x: u64,
-    y: u64,
-    new_blizzard_state_index: usize,
-    blizzard_states: &BlizzardStates,
-) -> eyre::Result<bool> {
-    if x == 0 && y == u64::MAX {
-        Ok(true)
-    } else if x == MAP_WIDTH - 1 && y == MAP_HEIGHT {
-        Ok(true)
-    } else {
-        Ok(!blizzard_states[new_blizzard_state_index][y.try_to::<usize>()?].blizzard_at(x)?)
-    }
+pub fn visualize(input: &str) -> eyre::Result<Blizzards> {
+    let mut lines = input.lines();
+    let _ = lines.next();
+    let (valley, blizzards) = parse_blizzards(&mut lines)?;
+    Ok(Blizzards { valley, blizzards })
 }
 
 fn find_shortest_path_to(
-    start_x: u64,
-    start_y: u64,
-    end_x: u64,
-    end_y: u64,
-    start_blizzard_state_index: usize,
-    blizzard_states: &BlizzardStates,
+    valley: Valley,
+    (start_x, start_y): (u64, u64),
+    (end_x, end_y): (u64, u64),
+    start_minute: usize,
+    blizzard_states: &[BlizzardState],
 ) -> eyre::Result<u32> {
-    let mut queue = VecDeque::from([(start_x, start_y, start_blizzard_state_index, 0)]);
-    let mut visited = HashSet::new();
-    while let Some((x, y, blizzard_state_index, time_spent)) = queue.pop_front() {
-        if x == end_x && y == end_y {
-            return Ok(time_spent);
-        }
-        if !visited.contains(&(x, y, blizzard_state_index)) {
-            visited.insert((x, y, blizzard_state_index));
-            let new_blizzard_state_index = (blizzard_state_index + 1) % NUM_BLIZZARD_STATES;
-            if let Some(new_x) = try_move_left(x, y) {
-                if !visited.contains(&(new_x, y, new_blizzard_state_index))
-                    && will_be_safe(new_x, y, new_blizzard_state_index, blizzard_states)?
-                {
-                    queue.push_back((new_x, y, new_blizzard_state_index, time_spent + 1));
-                }
-            }
-            if let Some(new_x) = try_move_right(x, y) {
-                if !visited.contains(&(new_x, y, new_blizzard_state_index))
-                    && will_be_safe(new_x, y, new_blizzard_state_index, blizzard_states)?
-                {
-                    queue.push_back((new_x, y, new_blizzard_state_index, time_spent + 1));
+    let outcome = search::bfs(
+        (start_x, start_y, start_minute % blizzard_states.len()),
+        |&(x, y, blizzard_state_index)| {
+            let new_blizzard_state_index = (blizzard_state_index + 1) % blizzard_states.len();
+            let moves = [
+                valley.try_move_left(x, y).map(|new_x| (new_x, y)),
+                valley.try_move_right(x, y).map(|new_x| (new_x, y)),
+                valley.try_move_up(x, y).map(|new_y| (x, new_y)),
+                valley.try_move_down(x, y).map(|new_y| (x, new_y)),
+                Some((x, y)),
+            ];
+
+            let mut next = Vec::with_capacity(moves.len());
+            for (x, y) in moves.into_iter().flatten() {
+                if valley.will_be_safe(x, y, new_blizzard_state_index, blizzard_states)? {
+                    next.push((x, y, new_blizzard_state_index));
                 }
             }
-            if let Some(new_y) = try_move_up(x, y) {
-                if !visited.contains(&(x, new_y, new_blizzard_state_index))
-                    && will_be_safe(x, new_y, new_blizzard_state_index, blizzard_states)?
-                {
-                    queue.push_back((x, new_y, new_blizzard_state_index, time_spent + 1));
-                }
+            Ok(next)
+        },
+        |&(x, y, _)| x == end_x && y == end_y,
+    )?;
+    log::debug!("the path search {}", outcome.metrics);
+
+    let steps = outcome
+        .cost()
+        .ok_or_else(|| eyre::eyre!("didn't find a shortest path"))?;
+    Ok(steps.try_to()?)
+}
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::util::cycle::Cycle;
+
+    const EXAMPLE: &str = "\
+#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#";
+
+    fn render(width: usize, spots: &[Option<Direction>]) -> String {
+        let mut rendered = String::from("#.");
+        rendered.extend(std::iter::repeat_n('#', width));
+        for row in spots.chunks(width) {
+            rendered.push_str("\n#");
+            rendered.extend(row.iter().map(|spot| match spot {
+                None => '.',
+                Some(direction) => direction.arrow(),
+            }));
+            rendered.push('#');
+        }
+        rendered.push('\n');
+        rendered.extend(std::iter::repeat_n('#', width));
+        rendered.push_str(".#");
+        rendered
+    }
+
+    fn spot() -> impl Strategy<Value = Option<Direction>> {
+        prop::option::weighted(0.3, prop::sample::select(Direction::CARDINALS.to_vec()))
+    }
+
+    fn parse(input: &str) -> eyre::Result<(Valley, HashSet<Blizzard>)> {
+        let mut lines = input.lines();
+        let _ = lines.next();
+        parse_blizzards(&mut lines)
+    }
+
+    #[test]
+    fn sizes_the_valley_from_the_input() {
+        let (valley, blizzards) = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            valley,
+            Valley {
+                width: 6,
+                height: 4
             }
-            if let Some(new_y) = try_move_down(x, y) {
-                if !visited.contains(&(x, new_y, new_blizzard_state_index))
-                    && will_be_safe(x, new_y, new_blizzard_state_index, blizzard_states)?
-                {
-                    queue.push_back((x, new_y, new_blizzard_state_index, time_spent + 1));
+        );
+        assert_eq!(blizzards.len(), 19);
+        assert_eq!(valley.max_blizzard_states().unwrap(), 12);
+    }
+
+    #[test]
+    fn keeps_the_states_until_the_blizzards_repeat() {
+        let rendered = EXAMPLE.replace(['<', '^', 'v'], ">");
+        let mut lines = rendered.lines();
+        let _ = lines.next();
+        // with every blizzard going across, they're back after going round
+        // once instead of lining up with ones going down
+        let (_, states) = parse_states(&mut lines).unwrap();
+        assert_eq!(states.len(), 6);
+    }
+
+    #[test]
+    fn rejects_broken_valleys() {
+        assert!(parse(&EXAMPLE.replace("#.<..<<#", "#.<.x<<#")).is_err());
+        assert!(parse(&EXAMPLE.replace("#.<..<<#", "#.<.E<<#")).is_err());
+        assert!(parse(&EXAMPLE.replace("#.<..<<#", "#.<..<#")).is_err());
+        assert!(parse(&EXAMPLE.replace("\n######.#", "")).is_err());
+    }
+
+    #[test]
+    fn blizzards_repeat_after_every_state() {
+        // a blizzard going across and one going down, which only line up
+        // with where they started again once both have gone all the way round
+        let (width, height) = (15, 6);
+        let mut spots = vec![None; width * height];
+        spots[0] = Some(Direction::East);
+        spots[width + 3] = Some(Direction::South);
+        let (valley, blizzards) = parse(&render(width, &spots)).unwrap();
+
+        let history = cycle::detect(
+            blizzards,
+            usize::MAX,
+            |blizzards| simulate_state_step(valley, blizzards),
+            fingerprint,
+            |_| 0,
+        );
+        assert_eq!(
+            history.cycle,
+            Some(Cycle {
+                start: 0,
+                length: valley.max_blizzard_states().unwrap()
+            })
+        );
+    }
+
+    proptest! {
+        // every case simulates all the blizzard states, so keep the count low
+        #![proptest_config(ProptestConfig::with_cases(8))]
+
+        #[test]
+        fn parses_rendered_blizzards(
+            (width, spots) in (2..40_usize, 1..20_usize).prop_flat_map(|(width, height)| {
+                (Just(width), prop::collection::vec(spot(), width * height))
+            }),
+        ) {
+            let rendered = render(width, &spots);
+            let mut lines = rendered.lines();
+            let _ = lines.next();
+            let (valley, states) = parse_states(&mut lines).unwrap();
+            prop_assert_eq!(valley.width, width as u64);
+
+            for (y, row) in spots.chunks(width).enumerate() {
+                for (x, spot) in row.iter().enumerate() {
+                    prop_assert_eq!(states[0].get(x, y), spot.is_some());
                 }
             }
-            if !visited.contains(&(x, y, new_blizzard_state_index))
-                && will_be_safe(x, y, new_blizzard_state_index, blizzard_states)?
-            {
-                queue.push_back((x, y, new_blizzard_state_index, time_spent + 1));
-            }
         }
     }
-
-    Err(eyre::eyre!("didn't find a shortest path"))
 }
@@ -1,3 +1,8 @@
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng};
+
+use crate::generate::InputGenerator;
+
 pub mod p1;
 
 fn str_to_snafu(string: &str) -> i64 {
@@ -69,3 +74,28 @@ fn int_to_snafu(mut num: i64) -> String {
 
     output
 }
+
+/// Generates `size` SNAFU numbers, each up to about as big as the real ones
+pub struct Generator;
+
+impl InputGenerator for Generator {
+    fn generate(&self, rng: &mut StdRng, size: usize) -> String {
+        (0..size)
+            .map(|_| int_to_snafu(rng.gen_range(1..=1_000_000_000_000)))
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn snafu_round_trips(num in 0..(1_i64 << 50)) {
+            prop_assert_eq!(str_to_snafu(&int_to_snafu(num)), num);
+        }
+    }
+}